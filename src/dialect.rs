@@ -0,0 +1,742 @@
+//! SQL dialects.
+//!
+//! Statements are written through a [`Writer`] that is generic over a [`Dialect`],
+//! which decides the parts of the output that differ between databases.
+//!
+//! ```
+//! use typed_sql::{Binding, Comparable, Query, Table, ToSql};
+//! use typed_sql::dialect::{MySql, Postgres};
+//!
+//! #[derive(Binding, Table)]
+//! struct User {
+//!     name: String
+//! }
+//!
+//! let plan = User::prepare("nameplan", |binds| {
+//!     User::table().update(|user| user.name.eq(binds.name))
+//! });
+//!
+//! assert_eq!(
+//!     plan.to_sql_for::<Postgres>(),
+//!     "PREPARE nameplan AS UPDATE users SET name = $1;"
+//! );
+//! assert_eq!(
+//!     plan.to_sql_for::<MySql>(),
+//!     "PREPARE nameplan FROM 'UPDATE users SET users.name = ?';"
+//! );
+//!
+//! let stmt = plan.execute(User { name: String::from("foo") });
+//! assert_eq!(stmt.to_sql_for::<Postgres>(), "EXECUTE nameplan('foo');");
+//! assert_eq!(
+//!     stmt.to_sql_for::<MySql>(),
+//!     "SET @p1 = 'foo'; EXECUTE nameplan USING @p1;"
+//! );
+//! ```
+//!
+//! Parts a dialect doesn't support fail to compile, such as prepared statements in SQL Server.
+//! ```compile_fail
+//! use typed_sql::{Binding, Comparable, Query, Table, ToSql};
+//! use typed_sql::dialect::MsSql;
+//!
+//! #[derive(Binding, Table)]
+//! struct User {
+//!     name: String
+//! }
+//!
+//! User::prepare("nameplan", |binds| {
+//!     User::table().update(|user| user.name.eq(binds.name))
+//! })
+//! .to_sql_for::<MsSql>();
+//! ```
+
+use crate::types::bind::Binder;
+use crate::types::computed::CastType;
+use crate::types::{Binding, Expr, Value};
+use crate::{ToSql, Writer};
+use std::fmt::Write;
+
+pub trait Dialect: Sized {
+    /// The character used to quote identifiers.
+    const QUOTE: char;
+
+    /// Whether columns in `UPDATE ... SET` are qualified with the table name.
+    const QUALIFY_SET: bool;
+
+    /// The case-insensitive `LIKE` operator.
+    ///
+    /// `LIKE` itself is case-insensitive in MySQL's default collations and for ASCII in SQLite.
+    const ILIKE: &'static str = "LIKE";
+
+    /// The null-safe equality operator, true when both sides are `NULL`.
+    const NOT_DISTINCT_FROM: &'static str = "IS NOT DISTINCT FROM";
+
+    /// Whether row values like `(a, b) > (1, 2)` can be compared.
+    const ROW_VALUES: bool = true;
+
+    /// The aggregate that checks if every value is true.
+    const BOOL_AND: &'static str = "BOOL_AND";
+
+    /// The aggregate that checks if any value is true.
+    const BOOL_OR: &'static str = "BOOL_OR";
+
+    /// The keywords starting a list of common table expressions that refer to themselves.
+    const WITH_RECURSIVE: &'static str = "WITH RECURSIVE";
+
+    /// The function counting the characters of a string.
+    const LENGTH: &'static str = "LENGTH";
+
+    /// Whether changed rows can be returned with `RETURNING`.
+    const RETURNING: bool = true;
+
+    /// Whether statements can be prepared by name with `PREPARE` and `EXECUTE`.
+    const PREPARE: bool = true;
+
+    /// Whether rows of both tables can be kept with a `FULL JOIN`.
+    const FULL_JOIN: bool = true;
+
+    /// Whether the first row of each group can be selected with `DISTINCT ON`.
+    const DISTINCT_ON: bool = true;
+
+    /// Whether values can be collected into an array with `ARRAY_AGG`.
+    const ARRAY_AGG: bool = true;
+
+    /// Whether inserts can update or skip rows that conflict with existing ones.
+    const UPSERT: bool = true;
+
+    /// Whether upserts are written with `ON CONFLICT`,
+    /// instead of `ON DUPLICATE KEY UPDATE` and `INSERT IGNORE`.
+    const ON_CONFLICT: bool = true;
+
+    /// Writes the placeholder for the `n`th bound parameter, starting at 1.
+    fn write_placeholder(sql: &mut Writer<Self>, n: usize);
+
+    /// Writes an identifier, quoting it if it can't be written bare.
+    fn write_ident(sql: &mut Writer<Self>, ident: &str) {
+        let mut chars = ident.chars();
+        let is_bare = matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c == '_')
+            && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+        if is_bare {
+            sql.push_str(ident);
+        } else {
+            sql.push(Self::QUOTE);
+            for c in ident.chars() {
+                if c == Self::QUOTE {
+                    sql.push(c);
+                }
+                sql.push(c);
+            }
+            sql.push(Self::QUOTE);
+        }
+    }
+
+    /// Writes a string literal, escaping it so its contents can't end the literal early.
+    fn write_str(sql: &mut Writer<Self>, s: &str) {
+        sql.push('\'');
+        sql.push_str(&s.replace('\'', "''"));
+        sql.push('\'');
+    }
+
+    fn write_bool(sql: &mut Writer<Self>, value: bool) {
+        sql.push_str(if value { "TRUE" } else { "FALSE" });
+    }
+
+    /// Writes a NaN or infinite float, as `NULL` by default because the dialect can't store it.
+    /// ```
+    /// use typed_sql::{Insertable, Query, Table, ToSql};
+    /// use typed_sql::dialect::MySql;
+    /// use typed_sql::types::Value;
+    ///
+    /// #[derive(Table, Insertable)]
+    /// struct Reading {
+    ///     value: f64
+    /// }
+    ///
+    /// let stmt = Reading::table().insert(Reading { value: f64::INFINITY });
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<MySql>(),
+    ///     "INSERT INTO readings(value) VALUES (NULL);"
+    /// );
+    /// assert_eq!(
+    ///     stmt.to_parameterized_for::<MySql>(),
+    ///     (String::from("INSERT INTO readings(value) VALUES (?);"), vec![Value::Null])
+    /// );
+    /// ```
+    fn write_non_finite(sql: &mut Writer<Self>, _value: f64) {
+        sql.push_str("NULL");
+    }
+
+    /// Binds a NaN or infinite float, as [`Value::Null`] by default
+    /// like [`write_non_finite`](Self::write_non_finite).
+    fn bind_non_finite(_value: f64) -> Value {
+        Value::Null
+    }
+
+    fn write_bytes(sql: &mut Writer<Self>, bytes: &[u8]) {
+        sql.push_str("X'");
+        write_hex(sql, bytes);
+        sql.push('\'');
+    }
+
+    fn write_distinct_from<A, L, R>(sql: &mut Writer<Self>, lhs: &L, rhs: &R)
+    where
+        L: Expr<A>,
+        R: Expr<A>,
+    {
+        lhs.write_expr(sql);
+        sql.push_str(" IS DISTINCT FROM ");
+        rhs.write_expr(sql);
+    }
+
+    fn write_string_agg<A, E: Expr<A>>(sql: &mut Writer<Self>, expr: &E, separator: &str) {
+        sql.push_str("STRING_AGG(");
+        expr.write_expr(sql);
+        sql.push_str(", ");
+        sql.write_str(separator);
+        sql.push(')');
+    }
+
+    /// Writes the boolean aggregate `function`, which is [`BOOL_AND`](Self::BOOL_AND)
+    /// or [`BOOL_OR`](Self::BOOL_OR), over `expr`.
+    fn write_bool_agg<A, E: Expr<A>>(sql: &mut Writer<Self>, function: &str, expr: &E) {
+        sql.push_str(function);
+        sql.push('(');
+        expr.write_expr(sql);
+        sql.push(')');
+    }
+
+    fn write_concat<A, L, R>(sql: &mut Writer<Self>, lhs: &L, rhs: &R)
+    where
+        L: Expr<A>,
+        R: Expr<A>,
+    {
+        sql.push_str("CONCAT(");
+        lhs.write_expr(sql);
+        sql.push_str(", ");
+        rhs.write_expr(sql);
+        sql.push(')');
+    }
+
+    /// Writes the select of the id generated by the last insert as a `BIGINT`,
+    /// for dialects without `RETURNING`.
+    fn write_last_insert_id(sql: &mut Writer<Self>);
+
+    /// Writes `column` of the row an upsert tried to insert.
+    fn write_excluded(sql: &mut Writer<Self>, column: &str) {
+        sql.push_str("excluded.");
+        sql.write_ident(column);
+    }
+
+    /// Writes the name of a type for `CAST(... AS type)`.
+    fn write_cast_type(sql: &mut Writer<Self>, ty: CastType) {
+        sql.push_str(match ty {
+            CastType::SmallInt => "SMALLINT",
+            CastType::Integer => "INTEGER",
+            CastType::BigInt => "BIGINT",
+            CastType::Real => "REAL",
+            CastType::Double => "DOUBLE PRECISION",
+            CastType::Text => "TEXT",
+            CastType::Boolean => "BOOLEAN",
+            CastType::Bytes => "BYTEA",
+        });
+    }
+
+    /// Writes the `LIMIT` and `OFFSET` at the end of a select, which `is_ordered` if it has an `ORDER BY`.
+    fn write_limit(
+        sql: &mut Writer<Self>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+        _is_ordered: bool,
+    ) {
+        if let Some(limit) = limit {
+            sql.write_fmt(format_args!(" LIMIT {}", limit)).unwrap();
+        }
+        if let Some(offset) = offset {
+            sql.write_fmt(format_args!(" OFFSET {}", offset)).unwrap();
+        }
+    }
+
+    /// Writes a select that's grouped as the operand of a compound select,
+    /// such as one with its own `LIMIT`.
+    fn write_compound_operand<S: ToSql>(sql: &mut Writer<Self>, select: &S) {
+        sql.push('(');
+        select.write_sql_unchecked(sql);
+        sql.push(')');
+    }
+
+    fn write_prepare<B, S>(sql: &mut Writer<Self>, name: &str, stmt: &S)
+    where
+        B: Binding,
+        S: ToSql,
+    {
+        sql.push_str("PREPARE ");
+        sql.write_ident(name);
+        B::write_types(sql);
+        sql.push_str(" AS ");
+        stmt.write_sql_unchecked(sql);
+    }
+
+    fn write_execute<B: Binding>(sql: &mut Writer<Self>, name: &str, binding: &B) {
+        sql.push_str("EXECUTE ");
+        sql.write_ident(name);
+        sql.push('(');
+        binding.write_values(sql);
+        sql.push(')');
+    }
+}
+
+/// PostgreSQL, the default dialect of [`ToSql::to_sql`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Postgres;
+
+impl Dialect for Postgres {
+    const QUOTE: char = '"';
+
+    const QUALIFY_SET: bool = false;
+
+    const ILIKE: &'static str = "ILIKE";
+
+    fn write_placeholder(sql: &mut Writer<Self>, n: usize) {
+        sql.write_fmt(format_args!("${}", n)).unwrap();
+    }
+
+    fn write_last_insert_id(sql: &mut Writer<Self>) {
+        sql.push_str("SELECT LASTVAL()");
+    }
+
+    /// Strings containing a backslash are written as escape strings (`E'...'`)
+    /// so they're read the same regardless of `standard_conforming_strings`.
+    fn write_str(sql: &mut Writer<Self>, s: &str) {
+        if s.contains('\\') {
+            sql.push('E');
+        }
+
+        sql.push('\'');
+        for c in s.chars() {
+            match c {
+                '\'' => sql.push_str("''"),
+                '\\' => sql.push_str("\\\\"),
+                c => sql.push(c),
+            }
+        }
+        sql.push('\'');
+    }
+
+    fn write_non_finite(sql: &mut Writer<Self>, value: f64) {
+        if value.is_nan() {
+            sql.push_str("'NaN'");
+        } else if value.is_sign_positive() {
+            sql.push_str("'Infinity'");
+        } else {
+            sql.push_str("'-Infinity'");
+        }
+    }
+
+    fn bind_non_finite(value: f64) -> Value {
+        Value::Float(value)
+    }
+
+    fn write_bytes(sql: &mut Writer<Self>, bytes: &[u8]) {
+        sql.push_str("decode('");
+        write_hex(sql, bytes);
+        sql.push_str("', 'hex')");
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MySql;
+
+impl Dialect for MySql {
+    const QUOTE: char = '`';
+
+    const QUALIFY_SET: bool = true;
+
+    const NOT_DISTINCT_FROM: &'static str = "<=>";
+
+    const BOOL_AND: &'static str = "MIN";
+
+    const BOOL_OR: &'static str = "MAX";
+
+    /// `LENGTH` counts bytes in MySQL.
+    const LENGTH: &'static str = "CHAR_LENGTH";
+
+    const RETURNING: bool = false;
+
+    const FULL_JOIN: bool = false;
+
+    const DISTINCT_ON: bool = false;
+
+    const ARRAY_AGG: bool = false;
+
+    /// MySQL upserts with `ON DUPLICATE KEY UPDATE`, which conflicts on any unique key.
+    const ON_CONFLICT: bool = false;
+
+    fn write_placeholder(sql: &mut Writer<Self>, _n: usize) {
+        sql.push('?');
+    }
+
+    fn write_last_insert_id(sql: &mut Writer<Self>) {
+        sql.push_str("SELECT CAST(LAST_INSERT_ID() AS SIGNED)");
+    }
+
+    /// Backslashes are escape characters in MySQL string literals.
+    fn write_str(sql: &mut Writer<Self>, s: &str) {
+        sql.push('\'');
+        for c in s.chars() {
+            match c {
+                '\'' => sql.push_str("''"),
+                '\\' => sql.push_str("\\\\"),
+                '\0' => sql.push_str("\\0"),
+                c => sql.push(c),
+            }
+        }
+        sql.push('\'');
+    }
+
+    /// MySQL only has the null-safe equality operator `<=>`.
+    fn write_distinct_from<A, L, R>(sql: &mut Writer<Self>, lhs: &L, rhs: &R)
+    where
+        L: Expr<A>,
+        R: Expr<A>,
+    {
+        sql.push_str("NOT (");
+        lhs.write_expr(sql);
+        sql.push_str(" <=> ");
+        rhs.write_expr(sql);
+        sql.push(')');
+    }
+
+    fn write_string_agg<A, E: Expr<A>>(sql: &mut Writer<Self>, expr: &E, separator: &str) {
+        sql.push_str("GROUP_CONCAT(");
+        expr.write_expr(sql);
+        sql.push_str(" SEPARATOR ");
+        sql.write_str(separator);
+        sql.push(')');
+    }
+
+    fn write_excluded(sql: &mut Writer<Self>, column: &str) {
+        sql.push_str("VALUES(");
+        sql.write_ident(column);
+        sql.push(')');
+    }
+
+    /// MySQL casts to a few general types, such as `SIGNED` for every integer.
+    fn write_cast_type(sql: &mut Writer<Self>, ty: CastType) {
+        sql.push_str(match ty {
+            CastType::SmallInt | CastType::Integer | CastType::BigInt | CastType::Boolean => {
+                "SIGNED"
+            }
+            CastType::Real => "FLOAT",
+            CastType::Double => "DOUBLE",
+            CastType::Text => "CHAR",
+            CastType::Bytes => "BINARY",
+        });
+    }
+
+    /// MySQL can't skip rows without a limit, so the largest one is used.
+    fn write_limit(
+        sql: &mut Writer<Self>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+        _is_ordered: bool,
+    ) {
+        match (limit, offset) {
+            (Some(limit), _) => sql.write_fmt(format_args!(" LIMIT {}", limit)).unwrap(),
+            (None, Some(_)) => sql.push_str(" LIMIT 18446744073709551615"),
+            (None, None) => {}
+        }
+        if let Some(offset) = offset {
+            sql.write_fmt(format_args!(" OFFSET {}", offset)).unwrap();
+        }
+    }
+
+    /// MySQL prepares statements from a string literal.
+    fn write_prepare<B, S>(sql: &mut Writer<Self>, name: &str, stmt: &S)
+    where
+        B: Binding,
+        S: ToSql,
+    {
+        let mut inner = Writer::<Self>::new();
+        stmt.write_sql_unchecked(&mut inner);
+
+        sql.push_str("PREPARE ");
+        sql.write_ident(name);
+        sql.push_str(" FROM ");
+        Self::write_str(sql, &inner.into_string());
+    }
+
+    /// MySQL executes statements with user variables, which are set first.
+    fn write_execute<B: Binding>(sql: &mut Writer<Self>, name: &str, binding: &B) {
+        let mut binder = Binder::default();
+        B::bindings(&mut binder);
+        let count = binder.count();
+
+        if count > 0 {
+            sql.push_str("SET ");
+            binding.write_values_with(sql, |sql, n| {
+                if n > 1 {
+                    sql.push_str(", ");
+                }
+                sql.write_fmt(format_args!("@p{} = ", n)).unwrap();
+            });
+            sql.push_str("; ");
+        }
+
+        sql.push_str("EXECUTE ");
+        sql.write_ident(name);
+        for n in 1..=count {
+            sql.push_str(if n > 1 { ", " } else { " USING " });
+            sql.write_fmt(format_args!("@p{}", n)).unwrap();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sqlite;
+
+impl Dialect for Sqlite {
+    const QUOTE: char = '"';
+
+    const QUALIFY_SET: bool = false;
+
+    const NOT_DISTINCT_FROM: &'static str = "IS";
+
+    const BOOL_AND: &'static str = "MIN";
+
+    const BOOL_OR: &'static str = "MAX";
+
+    /// SQLite only prepares statements through its API.
+    const PREPARE: bool = false;
+
+    const DISTINCT_ON: bool = false;
+
+    const ARRAY_AGG: bool = false;
+
+    fn write_placeholder(sql: &mut Writer<Self>, n: usize) {
+        sql.write_fmt(format_args!("?{}", n)).unwrap();
+    }
+
+    fn write_last_insert_id(sql: &mut Writer<Self>) {
+        sql.push_str("SELECT last_insert_rowid()");
+    }
+
+    fn write_distinct_from<A, L, R>(sql: &mut Writer<Self>, lhs: &L, rhs: &R)
+    where
+        L: Expr<A>,
+        R: Expr<A>,
+    {
+        lhs.write_expr(sql);
+        sql.push_str(" IS NOT ");
+        rhs.write_expr(sql);
+    }
+
+    fn write_string_agg<A, E: Expr<A>>(sql: &mut Writer<Self>, expr: &E, separator: &str) {
+        sql.push_str("GROUP_CONCAT(");
+        expr.write_expr(sql);
+        sql.push_str(", ");
+        sql.write_str(separator);
+        sql.push(')');
+    }
+
+    /// SQLite only has `CONCAT` since 3.44, so strings are joined with `||`.
+    fn write_concat<A, L, R>(sql: &mut Writer<Self>, lhs: &L, rhs: &R)
+    where
+        L: Expr<A>,
+        R: Expr<A>,
+    {
+        lhs.write_expr(sql);
+        sql.push_str(" || ");
+        rhs.write_expr(sql);
+    }
+
+    /// SQLite casts to the affinity of a type.
+    fn write_cast_type(sql: &mut Writer<Self>, ty: CastType) {
+        sql.push_str(match ty {
+            CastType::SmallInt | CastType::Integer | CastType::BigInt | CastType::Boolean => {
+                "INTEGER"
+            }
+            CastType::Real | CastType::Double => "REAL",
+            CastType::Text => "TEXT",
+            CastType::Bytes => "BLOB",
+        });
+    }
+
+    /// SQLite can't skip rows without a limit, where `-1` is no limit.
+    fn write_limit(
+        sql: &mut Writer<Self>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+        _is_ordered: bool,
+    ) {
+        match (limit, offset) {
+            (Some(limit), _) => sql.write_fmt(format_args!(" LIMIT {}", limit)).unwrap(),
+            (None, Some(_)) => sql.push_str(" LIMIT -1"),
+            (None, None) => {}
+        }
+        if let Some(offset) = offset {
+            sql.write_fmt(format_args!(" OFFSET {}", offset)).unwrap();
+        }
+    }
+
+    /// SQLite can't parenthesize the operands of a compound select, so they're selected from instead.
+    fn write_compound_operand<S: ToSql>(sql: &mut Writer<Self>, select: &S) {
+        sql.push_str("SELECT * FROM (");
+        select.write_sql_unchecked(sql);
+        sql.push(')');
+    }
+
+    /// SQLite reads out of range floats as infinity, but stores NaN as `NULL`.
+    fn write_non_finite(sql: &mut Writer<Self>, value: f64) {
+        if value.is_nan() {
+            sql.push_str("NULL");
+        } else if value.is_sign_positive() {
+            sql.push_str("9e999");
+        } else {
+            sql.push_str("-9e999");
+        }
+    }
+
+    fn bind_non_finite(value: f64) -> Value {
+        if value.is_nan() {
+            Value::Null
+        } else {
+            Value::Float(value)
+        }
+    }
+}
+
+/// Microsoft SQL Server.
+///
+/// ```
+/// use typed_sql::{Comparable, Query, Table, ToSql};
+/// use typed_sql::dialect::MsSql;
+///
+/// #[derive(Table)]
+/// struct Post {
+///     id: i64,
+///     draft: bool
+/// }
+///
+/// let stmt = Post::table()
+///     .select()
+///     .filter(|post| post.draft.eq(false))
+///     .order_by(|post| post.id)
+///     .limit(10)
+///     .offset(20);
+///
+/// assert_eq!(
+///     stmt.to_sql_for::<MsSql>(),
+///     "SELECT * FROM posts WHERE posts.draft = 0 ORDER BY posts.id \
+///     OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY;"
+/// );
+///
+/// let stmt = Post::table().select().limit(10);
+///
+/// assert_eq!(
+///     stmt.to_sql_for::<MsSql>(),
+///     "SELECT * FROM posts ORDER BY (SELECT NULL) OFFSET 0 ROWS FETCH NEXT 10 ROWS ONLY;"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MsSql;
+
+impl Dialect for MsSql {
+    const QUOTE: char = '"';
+
+    const QUALIFY_SET: bool = false;
+
+    const ROW_VALUES: bool = false;
+
+    /// SQL Server has no `RECURSIVE` keyword, any common table expression can refer to itself.
+    const WITH_RECURSIVE: &'static str = "WITH";
+
+    const BOOL_AND: &'static str = "MIN";
+
+    const BOOL_OR: &'static str = "MAX";
+
+    const LENGTH: &'static str = "LEN";
+
+    /// SQL Server returns rows with `OUTPUT` instead, which isn't supported.
+    const RETURNING: bool = false;
+
+    /// SQL Server prepares statements with `sp_prepare` instead, which isn't supported.
+    const PREPARE: bool = false;
+
+    const DISTINCT_ON: bool = false;
+
+    const ARRAY_AGG: bool = false;
+
+    /// SQL Server upserts with `MERGE` instead, which isn't supported.
+    const UPSERT: bool = false;
+
+    fn write_placeholder(sql: &mut Writer<Self>, n: usize) {
+        sql.write_fmt(format_args!("@p{}", n)).unwrap();
+    }
+
+    /// SQL Server has no boolean literals, so `bit` values are written as numbers.
+    fn write_bool(sql: &mut Writer<Self>, value: bool) {
+        sql.push(if value { '1' } else { '0' });
+    }
+
+    fn write_bytes(sql: &mut Writer<Self>, bytes: &[u8]) {
+        sql.push_str("0x");
+        write_hex(sql, bytes);
+    }
+
+    /// SQL Server can't aggregate `bit` values, so they're aggregated as integers.
+    fn write_bool_agg<A, E: Expr<A>>(sql: &mut Writer<Self>, function: &str, expr: &E) {
+        sql.push_str("CAST(");
+        sql.push_str(function);
+        sql.push_str("(CAST(");
+        expr.write_expr(sql);
+        sql.push_str(" AS INT)) AS BIT)");
+    }
+
+    fn write_last_insert_id(sql: &mut Writer<Self>) {
+        sql.push_str("SELECT CAST(SCOPE_IDENTITY() AS BIGINT)");
+    }
+
+    fn write_cast_type(sql: &mut Writer<Self>, ty: CastType) {
+        sql.push_str(match ty {
+            CastType::SmallInt => "SMALLINT",
+            CastType::Integer => "INT",
+            CastType::BigInt => "BIGINT",
+            CastType::Real => "REAL",
+            CastType::Double => "FLOAT",
+            CastType::Text => "NVARCHAR(MAX)",
+            CastType::Boolean => "BIT",
+            CastType::Bytes => "VARBINARY(MAX)",
+        });
+    }
+
+    /// SQL Server pages with `OFFSET ... FETCH`, which needs an `ORDER BY`,
+    /// so unordered selects are ordered by a constant.
+    fn write_limit(
+        sql: &mut Writer<Self>,
+        limit: Option<usize>,
+        offset: Option<usize>,
+        is_ordered: bool,
+    ) {
+        if limit.is_none() && offset.is_none() {
+            return;
+        }
+
+        if !is_ordered {
+            sql.push_str(" ORDER BY (SELECT NULL)");
+        }
+
+        sql.write_fmt(format_args!(" OFFSET {} ROWS", offset.unwrap_or(0)))
+            .unwrap();
+        if let Some(limit) = limit {
+            sql.write_fmt(format_args!(" FETCH NEXT {} ROWS ONLY", limit))
+                .unwrap();
+        }
+    }
+}
+
+fn write_hex<D: Dialect>(sql: &mut Writer<D>, bytes: &[u8]) {
+    for byte in bytes {
+        sql.write_fmt(format_args!("{:02x}", byte)).unwrap();
+    }
+}
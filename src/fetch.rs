@@ -1,36 +1,55 @@
-use crate::query::select::{SelectStatement, Selectable, WildCard};
-use crate::{CheckedSql, ToSql};
-use sqlx::{Database, Error, Executor, FromRow};
+use crate::dialect::Dialect;
+use crate::query::returning::{Returnable, Returning, ReturningId};
+use crate::query::select::aggregate::{Aggregate, AggregateFunction};
+use crate::query::select::queryable::{Count, Distinct, DistinctOn};
+use crate::query::select::window::{Over, Partition, WindowFunction, WindowOrder};
+use crate::query::select::{GroupOrder, Projection, SelectStatement, Selectable, WildCard};
+use crate::query::with::{CommonTables, WithQuery};
+use crate::types::case::{Branches, CaseExpr};
+use crate::types::computed::{Compute, Computed};
+use crate::types::{Expr, Field};
+use crate::{CheckedSql, Table, ToSql, Writer};
+use sqlx::{Acquire, ColumnIndex, Database, Decode, Error, Executor, FromRow, Row, Type};
 use std::future::Future;
 use std::pin::Pin;
 
+type FetchFuture<'out, T> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send + 'out>>;
+
+type FromRowFn<R, T> = fn(&R) -> Result<T, Error>;
+
 pub trait Fetch<'c, 'out, E>: ToSql + CheckedSql
 where
     E: Executor<'c> + 'out,
 {
-    type Output: for<'r> FromRow<'r, <E::Database as Database>::Row>;
+    type Output;
 
-    fn fetch_one(
-        &self,
-        exec: E,
-    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, Error>> + Send + 'out>> {
-        let sql = self.to_sql();
+    fn from_row(row: &<E::Database as Database>::Row) -> Result<Self::Output, Error>;
+
+    fn fetch_one(&self, exec: E) -> FetchFuture<'out, Self::Output>
+    where
+        E::Database: DialectDatabase,
+        Self::Output: 'out,
+    {
+        let sql = <E::Database as DialectDatabase>::to_sql(self);
+        let from_row: FromRowFn<_, Self::Output> = Self::from_row;
         Box::pin(async move {
             exec.fetch_one(sql.as_ref())
                 .await
-                .and_then(|row| FromRow::from_row(&row))
+                .and_then(|row| from_row(&row))
         })
     }
 
-    fn fetch_optional(
-        &self,
-        exec: E,
-    ) -> Pin<Box<dyn Future<Output = Result<Option<Self::Output>, Error>> + Send + 'out>> {
-        let sql = self.to_sql();
+    fn fetch_optional(&self, exec: E) -> FetchFuture<'out, Option<Self::Output>>
+    where
+        E::Database: DialectDatabase,
+        Self::Output: 'out,
+    {
+        let sql = <E::Database as DialectDatabase>::to_sql(self);
+        let from_row: FromRowFn<_, Self::Output> = Self::from_row;
         Box::pin(async move {
             exec.fetch_optional(sql.as_ref())
                 .await
-                .and_then(|row| row.as_ref().map(FromRow::from_row).transpose())
+                .and_then(|row| row.as_ref().map(from_row).transpose())
         })
     }
 }
@@ -42,4 +61,300 @@ where
     S::Table: for<'r> FromRow<'r, <E::Database as Database>::Row>,
 {
     type Output = S::Table;
+
+    fn from_row(row: &<E::Database as Database>::Row) -> Result<Self::Output, Error> {
+        FromRow::from_row(row)
+    }
+}
+
+impl<'c, 'out, E, S> Fetch<'c, 'out, E> for SelectStatement<S, Distinct<WildCard>>
+where
+    E: Executor<'c> + 'out,
+    S: Selectable,
+    S::Table: for<'r> FromRow<'r, <E::Database as Database>::Row>,
+{
+    type Output = S::Table;
+
+    fn from_row(row: &<E::Database as Database>::Row) -> Result<Self::Output, Error> {
+        FromRow::from_row(row)
+    }
+}
+
+/// Projections are fetched as their output, such as a tuple for a tuple of columns.
+impl<'c, 'out, E, S, Q> Fetch<'c, 'out, E> for SelectStatement<S, Q>
+where
+    E: Executor<'c> + 'out,
+    S: Selectable,
+    Q: FromColumns<<E::Database as Database>::Row>,
+{
+    type Output = Q::Output;
+
+    fn from_row(row: &<E::Database as Database>::Row) -> Result<Self::Output, Error> {
+        Q::from_columns(row, &mut 0)
+    }
+}
+
+impl<'c, 'out, E, W, Q> Fetch<'c, 'out, E> for WithQuery<W, Q>
+where
+    E: Executor<'c> + 'out,
+    W: CommonTables + CheckedSql,
+    Q: Fetch<'c, 'out, E>,
+{
+    type Output = Q::Output;
+
+    fn from_row(row: &<E::Database as Database>::Row) -> Result<Self::Output, Error> {
+        Q::from_row(row)
+    }
+}
+
+impl<'c, 'out, E, S> Fetch<'c, 'out, E> for Returning<S, WildCard>
+where
+    E: Executor<'c> + 'out,
+    S: Returnable + CheckedSql,
+    S::Table: Sized + for<'r> FromRow<'r, <E::Database as Database>::Row>,
+{
+    type Output = S::Table;
+
+    fn from_row(row: &<E::Database as Database>::Row) -> Result<Self::Output, Error> {
+        FromRow::from_row(row)
+    }
+}
+
+impl<'c, 'out, E, S, Q> Fetch<'c, 'out, E> for Returning<S, Q>
+where
+    E: Executor<'c> + 'out,
+    S: Returnable + CheckedSql,
+    Q: FromColumns<<E::Database as Database>::Row>,
+{
+    type Output = Q::Output;
+
+    fn from_row(row: &<E::Database as Database>::Row) -> Result<Self::Output, Error> {
+        Q::from_columns(row, &mut 0)
+    }
+}
+
+/// A database whose statements are written in `Dialect`.
+///
+/// ```
+/// # #[cfg(feature = "mysql")]
+/// # {
+/// use typed_sql::{Comparable, Query, Table, ToSql};
+/// use typed_sql::fetch::DialectDatabase;
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64,
+///     name: String
+/// }
+///
+/// let stmt = User::table()
+///     .select()
+///     .filter(|user| user.name.ilike("o'%"))
+///     .order_by(|user| user.id);
+///
+/// // The text sent by `Fetch::fetch_one` to a MySQL connection.
+/// assert_eq!(
+///     <sqlx::MySql as DialectDatabase>::to_sql(&stmt),
+///     "SELECT * FROM users WHERE users.name LIKE 'o''%' ORDER BY users.id;"
+/// );
+/// # }
+/// ```
+pub trait DialectDatabase: Database {
+    type Dialect: Dialect;
+
+    /// Writes `stmt` as it's sent to this database.
+    fn to_sql<S: ToSql + CheckedSql + ?Sized>(stmt: &S) -> String {
+        stmt.to_sql_for::<Self::Dialect>()
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl DialectDatabase for sqlx::Postgres {
+    type Dialect = crate::dialect::Postgres;
+}
+
+#[cfg(feature = "mysql")]
+impl DialectDatabase for sqlx::MySql {
+    type Dialect = crate::dialect::MySql;
+}
+
+impl<S, T, A> ReturningId<S, Field<T, A>>
+where
+    S: ToSql + CheckedSql,
+    T: Table,
+{
+    /// Runs this insert and fetches the generated id.
+    ///
+    /// Dialects without `RETURNING` select the id with a second statement,
+    /// so both run on the same connection acquired from `conn`.
+    pub fn fetch_id<'c, C>(&self, conn: C) -> FetchFuture<'c, i64>
+    where
+        C: Acquire<'c> + Send + 'c,
+        C::Database: DialectDatabase,
+        for<'e> &'e mut <C::Database as Database>::Connection: Executor<'e, Database = C::Database>,
+        i64: for<'r> Decode<'r, C::Database> + Type<C::Database>,
+        usize: ColumnIndex<<C::Database as Database>::Row>,
+    {
+        fetch_generated_id::<<C::Database as DialectDatabase>::Dialect, _, _>(self, conn)
+    }
+}
+
+fn fetch_generated_id<'c, D, Q, C>(stmt: &Q, conn: C) -> FetchFuture<'c, i64>
+where
+    D: Dialect,
+    Q: ToSql + CheckedSql,
+    C: Acquire<'c> + Send + 'c,
+    for<'e> &'e mut <C::Database as Database>::Connection: Executor<'e, Database = C::Database>,
+    i64: for<'r> Decode<'r, C::Database> + Type<C::Database>,
+    usize: ColumnIndex<<C::Database as Database>::Row>,
+{
+    let sql = stmt.to_sql_for::<D>();
+    let last_insert_id = if D::RETURNING {
+        None
+    } else {
+        let mut sql = Writer::<D>::new();
+        D::write_last_insert_id(&mut sql);
+        sql.push(';');
+        Some(sql.into_string())
+    };
+
+    Box::pin(async move {
+        let mut conn = conn.acquire().await?;
+        let row = if let Some(last_insert_id) = last_insert_id {
+            (&mut *conn).execute(sql.as_str()).await?;
+            (&mut *conn).fetch_one(last_insert_id.as_str()).await?
+        } else {
+            (&mut *conn).fetch_one(sql.as_str()).await?
+        };
+        row.try_get(0)
+    })
+}
+
+/// A projection that can be decoded from the columns of a row.
+pub trait FromColumns<R: Row>: Projection {
+    /// Decodes this projection from the columns starting at `index`,
+    /// advancing it past the columns that were read.
+    fn from_columns(row: &R, index: &mut usize) -> Result<Self::Output, Error>;
+}
+
+fn decode_column<R, A>(row: &R, index: &mut usize) -> Result<A, Error>
+where
+    R: Row,
+    A: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+    usize: ColumnIndex<R>,
+{
+    let value = row.try_get(*index)?;
+    *index += 1;
+    Ok(value)
+}
+
+impl<R, T, A> FromColumns<R> for Field<T, A>
+where
+    R: Row,
+    T: Table,
+    A: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+    usize: ColumnIndex<R>,
+{
+    fn from_columns(row: &R, index: &mut usize) -> Result<Self::Output, Error> {
+        decode_column(row, index)
+    }
+}
+
+impl<R, C> FromColumns<R> for Count<C>
+where
+    R: Row,
+    Count<C>: Projection<Output = i64>,
+    i64: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+    usize: ColumnIndex<R>,
+{
+    fn from_columns(row: &R, index: &mut usize) -> Result<Self::Output, Error> {
+        decode_column(row, index)
+    }
+}
+
+impl<R, F, X, A> FromColumns<R> for Aggregate<F, X, A>
+where
+    R: Row,
+    F: AggregateFunction<A>,
+    X: Expr<A>,
+    F::Output: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+    usize: ColumnIndex<R>,
+{
+    fn from_columns(row: &R, index: &mut usize) -> Result<Self::Output, Error> {
+        decode_column(row, index)
+    }
+}
+
+impl<R, F, P, O> FromColumns<R> for Over<F, P, O>
+where
+    R: Row,
+    F: WindowFunction,
+    P: Partition,
+    O: WindowOrder,
+    F::Output: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+    usize: ColumnIndex<R>,
+{
+    fn from_columns(row: &R, index: &mut usize) -> Result<Self::Output, Error> {
+        decode_column(row, index)
+    }
 }
+
+impl<R, A, W, X> FromColumns<R> for CaseExpr<A, W, X>
+where
+    R: Row,
+    W: Branches<A>,
+    X: Expr<A>,
+    A: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+    usize: ColumnIndex<R>,
+{
+    fn from_columns(row: &R, index: &mut usize) -> Result<Self::Output, Error> {
+        decode_column(row, index)
+    }
+}
+
+impl<R, X, A> FromColumns<R> for Computed<X, A>
+where
+    R: Row,
+    X: Compute,
+    A: for<'r> Decode<'r, R::Database> + Type<R::Database>,
+    usize: ColumnIndex<R>,
+{
+    fn from_columns(row: &R, index: &mut usize) -> Result<Self::Output, Error> {
+        decode_column(row, index)
+    }
+}
+
+impl<R: Row, Q: FromColumns<R>> FromColumns<R> for Distinct<Q> {
+    fn from_columns(row: &R, index: &mut usize) -> Result<Self::Output, Error> {
+        Q::from_columns(row, index)
+    }
+}
+
+impl<R: Row, O: GroupOrder, Q: FromColumns<R>> FromColumns<R> for DistinctOn<O, Q> {
+    fn from_columns(row: &R, index: &mut usize) -> Result<Self::Output, Error> {
+        Q::from_columns(row, index)
+    }
+}
+
+macro_rules! impl_from_columns_tuple {
+    ($($ty:ident),*) => {
+        impl<R: Row, $($ty: FromColumns<R>),*> FromColumns<R> for ($($ty,)*) {
+            fn from_columns(row: &R, index: &mut usize) -> Result<Self::Output, Error> {
+                Ok(($($ty::from_columns(row, index)?,)*))
+            }
+        }
+    };
+}
+
+impl_from_columns_tuple!(P1);
+impl_from_columns_tuple!(P1, P2);
+impl_from_columns_tuple!(P1, P2, P3);
+impl_from_columns_tuple!(P1, P2, P3, P4);
+impl_from_columns_tuple!(P1, P2, P3, P4, P5);
+impl_from_columns_tuple!(P1, P2, P3, P4, P5, P6);
+impl_from_columns_tuple!(P1, P2, P3, P4, P5, P6, P7);
+impl_from_columns_tuple!(P1, P2, P3, P4, P5, P6, P7, P8);
+impl_from_columns_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9);
+impl_from_columns_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10);
+impl_from_columns_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11);
+impl_from_columns_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12);
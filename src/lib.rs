@@ -2,7 +2,7 @@
 //! See [`Query`] for available methods.
 //!
 //! ```
-//! use typed_sql::{Query, Table, ToSql};
+//! use typed_sql::{Comparable, Query, Table, ToSql};
 //!
 //! #[derive(Table)]
 //! struct User {
@@ -27,28 +27,30 @@
 //! );
 //! ```
 //! ## Injections
-//! Queries with user input strings are vulnerable to SQL injections
-//! and therefore must be serialized with [`ToSql::to_sql_unchecked`].
+//! String values are escaped for the dialect they're written in,
+//! so queries with user input can be serialized with [`ToSql::to_sql`].
 //!
 //! ```
 //! use typed_sql::{Insertable, Query, Table, ToSql};
+//! use typed_sql::dialect::MySql;
 //!
 //! #[derive(Table, Insertable)]
 //! struct User {
 //!     name: String
 //! }
 //!
-//! let stmt = User::table().insert(User { name: String::from("untrusted") });
+//! let stmt = User::table().insert(User { name: String::from("O'Brien\\") });
 //!
+//! assert_eq!(stmt.to_sql(), "INSERT INTO users(name) VALUES (E'O''Brien\\\\');");
 //! assert_eq!(
-//!     stmt.to_sql_unchecked(),
-//!     "INSERT INTO users(name) VALUES ('untrusted');"
+//!     stmt.to_sql_for::<MySql>(),
+//!     "INSERT INTO users(name) VALUES ('O''Brien\\\\');"
 //! );
 //! ```
 //!
-//! To avoid this use prepared statements with [`Binding`].
+//! Values can also be sent separately from the query with prepared statements using [`Binding`].
 //! ```
-//! use typed_sql::{Binding, Query, Table, ToSql};
+//! use typed_sql::{Binding, Comparable, Query, Table, ToSql};
 //!
 //! #[derive(Binding, Table)]
 //! struct User {
@@ -61,12 +63,31 @@
 //!
 //! assert_eq!(
 //!     id_plan.to_sql(),
-//!     "PREPARE idplan AS UPDATE users SET users.name = $1;"
+//!     "PREPARE idplan AS UPDATE users SET name = $1;"
 //! );
 //!
 //! let stmt = id_plan.execute(User { name: String::from("foo") });
 //! assert_eq!(stmt.to_sql(), "EXECUTE idplan('foo');");
 //! ```
+//!
+//! Or serialize with [`ToSql::to_parameterized`] to send every value as a parameter.
+//! ```
+//! use typed_sql::{Insertable, Query, Table, ToSql};
+//! use typed_sql::types::Value;
+//!
+//! #[derive(Table, Insertable)]
+//! struct User {
+//!     name: String
+//! }
+//!
+//! let stmt = User::table().insert(User { name: String::from("untrusted") });
+//!
+//! let (sql, params) = stmt.to_parameterized();
+//! assert_eq!(sql, "INSERT INTO users(name) VALUES ($1);");
+//! assert_eq!(params, [Value::Text(String::from("untrusted"))]);
+//! ```
+
+pub mod dialect;
 
 #[cfg(feature = "sqlx")]
 pub mod fetch;
@@ -74,10 +95,10 @@ pub mod fetch;
 pub use fetch::Fetch;
 
 pub mod query;
-pub use query::{Insertable, Join, Query, Queryable};
+pub use query::{Comparable, Insertable, Join, Query, Queryable};
 
 mod sql;
-pub use sql::{CheckedSql, ToSql};
+pub use sql::{CheckedSql, ToSql, Writer};
 
 pub mod table;
 pub use table::Table;
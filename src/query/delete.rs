@@ -1,5 +1,6 @@
+use crate::dialect::Dialect;
 use crate::table::Table;
-use crate::ToSql;
+use crate::{CheckedSql, ToSql, Writer};
 use std::marker::PhantomData;
 
 pub struct Delete<T: ?Sized> {
@@ -15,12 +16,14 @@ impl<T: ?Sized> Delete<T> {
 }
 
 impl<T: Table + ?Sized> ToSql for Delete<T> {
-    fn write_sql_unchecked(&self, sql: &mut String) {
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
         sql.push_str("DELETE FROM ");
-        sql.push_str(T::NAME);
+        T::write_table(sql);
     }
 }
 
+impl<T: ?Sized> CheckedSql for Delete<T> {}
+
 impl<T: ?Sized> Clone for Delete<T> {
     fn clone(&self) -> Self {
         *self
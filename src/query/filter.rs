@@ -2,7 +2,8 @@ use super::delete::Delete;
 use super::select::{Select, Selectable};
 use super::update::Update;
 use super::Predicate;
-use crate::{sql::CheckedSql, Table, ToSql};
+use crate::dialect::Dialect;
+use crate::{sql::CheckedSql, Table, ToSql, Writer};
 
 pub trait Filterable {
     type Fields: Default;
@@ -37,7 +38,7 @@ where
     S: ToSql,
     P: Predicate,
 {
-    fn write_sql_unchecked(&self, sql: &mut String) {
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.stmt.write_sql_unchecked(sql);
         sql.push_str(" WHERE ");
         self.predicate.write_predicate(sql);
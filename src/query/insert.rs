@@ -1,19 +1,20 @@
 use super::Select;
-use crate::{CheckedSql, Table, ToSql};
+use crate::dialect::Dialect;
+use crate::{CheckedSql, Table, ToSql, Writer};
 use std::marker::PhantomData;
 
 pub trait Insertable {
-    fn write_columns(sql: &mut String);
+    fn write_columns<D: Dialect>(sql: &mut Writer<D>);
 
-    fn write_values(&self, sql: &mut String);
+    fn write_values<D: Dialect>(&self, sql: &mut Writer<D>);
 }
 
 impl<I: Insertable> Insertable for &I {
-    fn write_columns(sql: &mut String) {
+    fn write_columns<D: Dialect>(sql: &mut Writer<D>) {
         I::write_columns(sql);
     }
 
-    fn write_values(&self, sql: &mut String) {
+    fn write_values<D: Dialect>(&self, sql: &mut Writer<D>) {
         (*self).write_values(sql);
     }
 }
@@ -47,11 +48,11 @@ where
     S: Select,
     I: Insertable + ?Sized,
 {
-    fn write_columns(sql: &mut String) {
+    fn write_columns<D: Dialect>(sql: &mut Writer<D>) {
         I::write_columns(sql);
     }
 
-    fn write_values(&self, sql: &mut String) {
+    fn write_values<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.select.write_sql_unchecked(sql);
     }
 }
@@ -71,41 +72,38 @@ where
             _table: PhantomData,
         }
     }
+}
 
-    fn write_insert<I2: Insertable>(&self, sql: &mut String) {
-        sql.push_str("INSERT INTO ");
-        sql.push_str(T::NAME);
-        sql.push('(');
-        I2::write_columns(sql);
-        sql.push(')');
-        sql.push_str(" VALUES ");
-    }
+/// The rows written after `VALUES`, either one [`Insertable`] or many of them.
+pub trait InsertRows {
+    fn write_columns<D: Dialect>(&self, sql: &mut Writer<D>);
+
+    fn write_rows<D: Dialect>(&self, sql: &mut Writer<D>);
 }
 
-impl<T, I> ToSql for InsertStatement<T, I>
-where
-    T: Table + ?Sized,
-    I: Insertable,
-{
-    fn write_sql_unchecked(&self, sql: &mut String) {
-        self.write_insert::<I>(sql);
+impl<I: Insertable> InsertRows for I {
+    fn write_columns<D: Dialect>(&self, sql: &mut Writer<D>) {
+        <I as Insertable>::write_columns(sql);
+    }
 
+    fn write_rows<D: Dialect>(&self, sql: &mut Writer<D>) {
         sql.push('(');
-        self.values.write_values(sql);
+        self.write_values(sql);
         sql.push(')');
     }
 }
 
-impl<T, I> ToSql for InsertStatement<T, Values<I>>
+impl<I> InsertRows for Values<I>
 where
-    T: Table,
     I: IntoIterator + Clone,
     I::Item: Insertable,
 {
-    fn write_sql_unchecked(&self, sql: &mut String) {
-        self.write_insert::<I::Item>(sql);
+    fn write_columns<D: Dialect>(&self, sql: &mut Writer<D>) {
+        <I::Item as Insertable>::write_columns(sql);
+    }
 
-        let mut values = self.values.iter.clone().into_iter().peekable();
+    fn write_rows<D: Dialect>(&self, sql: &mut Writer<D>) {
+        let mut values = self.iter.clone().into_iter().peekable();
         loop {
             if let Some(value) = values.next() {
                 sql.push('(');
@@ -122,4 +120,44 @@ where
     }
 }
 
-impl<T, I: CheckedSql> CheckedSql for InsertSelect<T, I> {}
+impl<T, V> InsertStatement<T, V>
+where
+    T: Table + ?Sized,
+    V: InsertRows,
+{
+    /// Writes this insert, skipping rows that conflict with existing ones if `ignore` is set.
+    pub(crate) fn write_insert<D: Dialect>(&self, sql: &mut Writer<D>, ignore: bool) {
+        if ignore {
+            sql.push_str("INSERT IGNORE INTO ");
+        } else {
+            sql.push_str("INSERT INTO ");
+        }
+        T::write_table(sql);
+        sql.push('(');
+        self.values.write_columns(sql);
+        sql.push(')');
+        sql.push_str(" VALUES ");
+        self.values.write_rows(sql);
+    }
+}
+
+impl<T, V> ToSql for InsertStatement<T, V>
+where
+    T: Table + ?Sized,
+    V: InsertRows,
+{
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_insert(sql, false);
+    }
+}
+
+impl<S: CheckedSql, I: ?Sized> CheckedSql for InsertSelect<S, I> {}
+
+impl<I> CheckedSql for Values<I>
+where
+    I: IntoIterator,
+    I::Item: CheckedSql,
+{
+}
+
+impl<T: ?Sized, I: CheckedSql> CheckedSql for InsertStatement<T, I> {}
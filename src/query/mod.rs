@@ -14,24 +14,40 @@ pub use insert::Insertable;
 use insert::{InsertSelect, InsertStatement, Values};
 
 pub mod predicate;
-pub use predicate::Predicate;
-use predicate::{And, Or};
+pub use predicate::{exists, not_exists, Comparable, Predicate};
+use predicate::{And, Group, Not, Or};
 
 pub mod prepare;
 use prepare::Prepare;
 
+pub mod returning;
+use returning::{Returnable, Returning};
+
 pub mod select;
-use select::queryable::{Count, WildCard, WriteQueryable};
-use select::{GroupBy, GroupOrder, Limit, Order, OrderBy, SelectStatement, Selectable};
+pub use select::aggregate::{
+    array_agg, avg, bool_and, bool_or, count, count_all, max, min, string_agg, sum,
+};
+use select::compound::{Except, Intersect, SameRows, Union, UnionAll};
+use select::queryable::{Count, Projection, Scalar, WildCard, WriteQueryable};
+pub use select::window::{dense_rank, lag, lead, rank, row_number, window};
+use select::{
+    After, Compound, GroupBy, GroupOrder, Keyset, Limit, Order, OrderBy, SelectStatement,
+    Selectable, Subquery,
+};
 pub use select::{Join, Joined, Queryable, Select};
 
 pub mod update;
 use update::{Update, UpdateSet};
 
+pub mod upsert;
+
+pub mod with;
+pub use with::{with, with_columns, with_recursive};
+
 pub trait Query: Sized {
     /// # Examples
     /// ```
-    /// use typed_sql::{Binding, Query, Table, ToSql};
+    /// use typed_sql::{Binding, Comparable, Query, Table, ToSql};
     ///
     /// #[derive(Table)]
     /// struct Post {
@@ -55,7 +71,40 @@ pub trait Query: Sized {
     ///     "PREPARE postplan AS SELECT * FROM posts WHERE posts.id = $1;"
     /// );
     /// ```
-    fn prepare<F, S>(name: &str, f: F) -> Prepare<Self, S>
+    ///
+    /// Values are written inline even when parameterized, since a `PREPARE` can't have parameters.
+    /// ```
+    /// use typed_sql::{Binding, Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     id: i64,
+    ///     content: String
+    /// }
+    ///
+    /// #[derive(Binding)]
+    /// struct PostBinding {
+    ///     id: i64
+    /// }
+    ///
+    /// let stmt = PostBinding::prepare("postplan", |binds| {
+    ///     Post::table()
+    ///         .select()
+    ///         .filter(|post| post.content.eq("draft").and(post.id.eq(binds.id)))
+    /// });
+    ///
+    /// assert_eq!(
+    ///     stmt.to_parameterized(),
+    ///     (
+    ///         String::from(
+    ///             "PREPARE postplan AS SELECT * FROM posts \
+    ///             WHERE posts.content = 'draft' AND posts.id = $1;"
+    ///         ),
+    ///         vec![]
+    ///     )
+    /// );
+    /// ```
+    fn prepare<F, S>(name: &str, f: F) -> Prepare<'_, Self, S>
     where
         Self: Binding,
         F: FnOnce(Self::Bindings) -> S,
@@ -67,7 +116,7 @@ pub trait Query: Sized {
 
     /// # Examples
     /// ```
-    /// use typed_sql::{Query, Table, ToSql};
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
     ///
     /// #[derive(Table)]
     /// struct Post {
@@ -77,7 +126,7 @@ pub trait Query: Sized {
     /// let stmt = Post::table().select().filter(|p| p.content.eq("foo"));
     ///
     /// assert_eq!(
-    ///     stmt.to_sql_unchecked(),
+    ///     stmt.to_sql(),
     ///     "SELECT * FROM posts WHERE posts.content = 'foo';"
     /// );
     /// ```
@@ -151,6 +200,114 @@ pub trait Query: Sized {
         self.query(Count::new(f(Default::default())))
     }
 
+    /// Uses this select of a single column as a value, such as in a comparison.
+    ///
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     id: i64,
+    ///     views: i64
+    /// }
+    ///
+    /// let stmt = Post::table()
+    ///     .select()
+    ///     .filter(|post| post.views.gt(Post::table().count(|_| {}).subquery()));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM posts WHERE posts.views > (SELECT COUNT(*) FROM posts);"
+    /// );
+    /// ```
+    fn subquery(self) -> Subquery<Self, <Self::Queryable as Projection>::Output>
+    where
+        Self: Select,
+        Self::Queryable: Scalar,
+    {
+        Subquery::new(self)
+    }
+
+    /// Selects the columns and expressions returned by `f`,
+    /// which are fetched as a tuple of their types.
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    /// use typed_sql::query::max;
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String,
+    ///     age: Option<i32>
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .select_with(|user| (user.id, user.name))
+    ///     .filter(|user| user.age.gt(17));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT users.id,users.name FROM users WHERE users.age > 17;"
+    /// );
+    ///
+    /// let stmt = User::table().select_with(|user| ((user.name, user.age), max(user.id)));
+    ///
+    /// assert_eq!(stmt.to_sql(), "SELECT users.name,users.age,MAX(users.id) FROM users;");
+    /// ```
+    fn select_with<F, P>(self, f: F) -> SelectStatement<Self, P>
+    where
+        Self: Selectable,
+        F: FnOnce(Self::Fields) -> P,
+        P: Projection,
+    {
+        self.query(f(Default::default()))
+    }
+
+    /// Selects an aggregate of the rows, such as [`sum`](crate::query::sum).
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    /// use typed_sql::query::{bool_or, min};
+    /// use typed_sql::dialect::MsSql;
+    ///
+    /// #[derive(Table)]
+    /// struct Task {
+    ///     project_id: i64,
+    ///     due: Option<i64>,
+    ///     done: bool
+    /// }
+    ///
+    /// let stmt = Task::table()
+    ///     .aggregate(|task| min(task.due))
+    ///     .filter(|task| task.done.eq(false));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT MIN(tasks.due) FROM tasks WHERE tasks.done = FALSE;"
+    /// );
+    ///
+    /// let stmt = Task::table()
+    ///     .aggregate(|task| bool_or(task.done))
+    ///     .group_by(|task| task.project_id);
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT BOOL_OR(tasks.done) FROM tasks GROUP BY tasks.project_id;"
+    /// );
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<MsSql>(),
+    ///     "SELECT CAST(MAX(CAST(tasks.done AS INT)) AS BIT) FROM tasks GROUP BY tasks.project_id;"
+    /// );
+    /// ```
+    fn aggregate<F, G>(self, f: F) -> SelectStatement<Self, G>
+    where
+        Self: Selectable,
+        F: FnOnce(Self::Fields) -> G,
+        G: Scalar,
+    {
+        self.query(f(Default::default()))
+    }
+
     /// ```
     /// use typed_sql::{Insertable, Query, Table, ToSql};
     ///
@@ -168,7 +325,7 @@ pub trait Query: Sized {
     /// let stmt = User::table().insert(UserInsert { name: "Matt" });
     ///
     /// assert_eq!(
-    ///     stmt.to_sql_unchecked(),
+    ///     stmt.to_sql(),
     ///     "INSERT INTO users(name) VALUES ('Matt');"
     /// );
     /// ```
@@ -199,7 +356,7 @@ pub trait Query: Sized {
     }
 
     /// ```
-    /// use typed_sql::{Query, Table, ToSql};
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
     ///
     /// #[derive(Table)]
     /// struct Post {
@@ -212,9 +369,9 @@ pub trait Query: Sized {
     ///     .filter(|p| p.id.eq(1));
     ///
     /// assert_eq!(
-    ///     stmt.to_sql_unchecked(),
+    ///     stmt.to_sql(),
     ///     "UPDATE posts \
-    ///     SET posts.id = 2,posts.name = 'foo' \
+    ///     SET id = 2,name = 'foo' \
     ///     WHERE posts.id = 1;"
     /// );
     /// ```
@@ -228,7 +385,7 @@ pub trait Query: Sized {
     }
 
     /// ```
-    /// use typed_sql::{Query, Table, ToSql};
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
     ///
     /// #[derive(Table)]
     /// struct Post {
@@ -246,6 +403,96 @@ pub trait Query: Sized {
         Delete::new()
     }
 
+    /// Returns the `columns` of the rows changed by this statement.
+    ///
+    /// Inserts in dialects without `RETURNING` can only return a generated id,
+    /// with [`InsertStatement::returning_id`].
+    /// ```
+    /// use typed_sql::{Comparable, Insertable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table, Insertable)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String
+    /// }
+    ///
+    /// #[derive(Insertable)]
+    /// struct NewUser {
+    ///     name: String
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .insert(NewUser { name: String::from("Ann") })
+    ///     .returning(|user| user.id);
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "INSERT INTO users(name) VALUES ('Ann') RETURNING users.id;"
+    /// );
+    ///
+    /// let stmt = User::table()
+    ///     .update(|user| user.name.eq("Bob"))
+    ///     .filter(|user| user.id.eq(1))
+    ///     .returning(|user| (user.id, user.name));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "UPDATE users SET name = 'Bob' WHERE users.id = 1 RETURNING users.id,users.name;"
+    /// );
+    /// ```
+    ///
+    /// Updates and deletes can't return rows in MySQL or SQL Server.
+    /// ```compile_fail
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    /// use typed_sql::dialect::MySql;
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String
+    /// }
+    ///
+    /// User::table()
+    ///     .update(|user| user.name.eq("Bob"))
+    ///     .returning(|user| user.id)
+    ///     .to_sql_for::<MySql>();
+    /// ```
+    fn returning<F, P>(self, f: F) -> Returning<Self, P>
+    where
+        Self: Returnable,
+        F: FnOnce(<<Self as Returnable>::Table as Table>::Fields) -> P,
+        P: Projection,
+    {
+        Returning::new(self, f(Default::default()))
+    }
+
+    /// Returns every column of the rows changed by this statement.
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct Session {
+    ///     id: i64,
+    ///     expired: bool
+    /// }
+    ///
+    /// let stmt = Session::table()
+    ///     .delete()
+    ///     .filter(|session| session.expired.eq(true))
+    ///     .returning_all();
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "DELETE FROM sessions WHERE sessions.expired = TRUE RETURNING *;"
+    /// );
+    /// ```
+    fn returning_all(self) -> Returning<Self, WildCard>
+    where
+        Self: Returnable,
+    {
+        Returning::new(self, WildCard)
+    }
+
     fn filter<F, P>(self, f: F) -> Filter<Self, P>
     where
         Self: Filterable,
@@ -255,7 +502,7 @@ pub trait Query: Sized {
     }
 
     /// ```
-    /// use typed_sql::{Query, Table, ToSql};
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
     ///
     /// #[derive(Table)]
     /// struct User {
@@ -269,6 +516,34 @@ pub trait Query: Sized {
     ///     "SELECT * FROM users WHERE users.id != 2 AND users.id < 5;"
     /// );
     /// ```
+    /// ## Precedence
+    /// Predicates are grouped to keep the structure of the combinators.
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .select()
+    ///     .filter(|user| user.id.eq(1).or(user.id.eq(2)).and(user.id.gt(0)));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM users WHERE (users.id = 1 OR users.id = 2) AND users.id > 0;"
+    /// );
+    ///
+    /// let stmt = User::table()
+    ///     .select()
+    ///     .filter(|user| user.id.gt(0).and(user.id.eq(1).or(user.id.eq(2))));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM users WHERE users.id > 0 AND (users.id = 1 OR users.id = 2);"
+    /// );
+    /// ```
     fn and<P>(self, predicate: P) -> And<Self, P>
     where
         Self: Predicate,
@@ -281,7 +556,7 @@ pub trait Query: Sized {
     }
 
     /// ```
-    /// use typed_sql::{Query, Table, ToSql};
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
     ///
     /// #[derive(Table)]
     /// struct User {
@@ -296,6 +571,26 @@ pub trait Query: Sized {
     ///     stmt.to_sql_unchecked(),
     ///     "SELECT * FROM users WHERE users.id = 1 OR users.id = 3;"
     /// );
+    /// ```
+    /// ## Precedence
+    /// `AND` binds tighter than `OR` in SQL, so neither side needs grouping.
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .select()
+    ///     .filter(|user| user.id.gt(0).and(user.id.lt(5)).or(user.id.eq(9).and(user.id.neq(3))));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM users WHERE users.id > 0 AND users.id < 5 OR users.id = 9 AND users.id != 3;"
+    /// );
+    /// ```
     fn or<P>(self, predicate: P) -> Or<Self, P>
     where
         Self: Predicate,
@@ -307,6 +602,55 @@ pub trait Query: Sized {
         }
     }
 
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .select()
+    ///     .filter(|user| user.id.eq(1).or(user.id.eq(2)).not().and(user.id.gt(0)));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM users WHERE NOT (users.id = 1 OR users.id = 2) AND users.id > 0;"
+    /// );
+    /// ```
+    fn not(self) -> Not<Self>
+    where
+        Self: Predicate,
+    {
+        Not { predicate: self }
+    }
+
+    /// Groups this predicate in parentheses, even where precedence doesn't require it.
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .select()
+    ///     .filter(|user| user.id.gt(0).and(user.id.lt(5)).group().or(user.id.eq(9)));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM users WHERE (users.id > 0 AND users.id < 5) OR users.id = 9;"
+    /// );
+    /// ```
+    fn group(self) -> Group<Self>
+    where
+        Self: Predicate,
+    {
+        Group { predicate: self }
+    }
+
     /// # Examples
     /// ```
     /// use typed_sql::{Table, ToSql, Query};
@@ -394,11 +738,191 @@ pub trait Query: Sized {
         OrderBy::new(self, f(Default::default()))
     }
 
+    /// # Examples
+    /// ```
+    /// use typed_sql::{Query, Table, ToSql};
+    /// use typed_sql::dialect::{MySql, Postgres};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64
+    /// }
+    ///
+    /// let stmt = User::table().select().order_by(|user| user.id).limit(10).offset(30);
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM users ORDER BY users.id LIMIT 10 OFFSET 30;"
+    /// );
+    ///
+    /// let stmt = User::table().select().order_by(|user| user.id).offset(30);
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<Postgres>(),
+    ///     "SELECT * FROM users ORDER BY users.id OFFSET 30;"
+    /// );
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<MySql>(),
+    ///     "SELECT * FROM users ORDER BY users.id LIMIT 18446744073709551615 OFFSET 30;"
+    /// );
+    /// ```
     fn limit(self, limit: usize) -> Limit<Self>
     where
         Self: Select,
     {
-        Limit::new(self, limit)
+        Limit::new(self, Some(limit), None)
+    }
+
+    fn offset(self, offset: usize) -> Limit<Self>
+    where
+        Self: Select,
+    {
+        Limit::new(self, None, Some(offset))
+    }
+
+    /// Combines the rows of this select and `other`, without duplicates.
+    ///
+    /// Both selects must return the same type of rows.
+    /// ```
+    /// use typed_sql::{Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String
+    /// }
+    ///
+    /// #[derive(Table)]
+    /// struct Admin {
+    ///     user_id: i64
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .select_with(|user| user.id)
+    ///     .union(Admin::table().select_with(|admin| admin.user_id));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT users.id FROM users UNION SELECT admins.user_id FROM admins;"
+    /// );
+    /// ```
+    /// ```compile_fail
+    /// use typed_sql::{Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String
+    /// }
+    ///
+    /// User::table()
+    ///     .select_with(|user| user.id)
+    ///     .union(User::table().select_with(|user| user.name));
+    /// ```
+    fn union<S>(self, other: S) -> Compound<Self, S, Union>
+    where
+        Self: Select + SameRows<S>,
+        S: Select,
+    {
+        Compound::new(self, other)
+    }
+
+    fn union_all<S>(self, other: S) -> Compound<Self, S, UnionAll>
+    where
+        Self: Select + SameRows<S>,
+        S: Select,
+    {
+        Compound::new(self, other)
+    }
+
+    fn intersect<S>(self, other: S) -> Compound<Self, S, Intersect>
+    where
+        Self: Select + SameRows<S>,
+        S: Select,
+    {
+        Compound::new(self, other)
+    }
+
+    /// Selects the rows of this select that aren't in `other`.
+    /// ```
+    /// use typed_sql::{Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String
+    /// }
+    ///
+    /// #[derive(Table)]
+    /// struct Ban {
+    ///     user_id: i64
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .select_with(|user| user.id)
+    ///     .except(Ban::table().select_with(|ban| ban.user_id));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT users.id FROM users EXCEPT SELECT bans.user_id FROM bans;"
+    /// );
+    /// ```
+    fn except<S>(self, other: S) -> Compound<Self, S, Except>
+    where
+        Self: Select + SameRows<S>,
+        S: Select,
+    {
+        Compound::new(self, other)
+    }
+
+    /// Filters for the rows after `values` in this order, to fetch the next page
+    /// of a select without an `OFFSET`.
+    ///
+    /// The ordered columns should be unique and not `NULL` together,
+    /// such as by ending with the primary key.
+    /// ```
+    /// use typed_sql::{Query, Table, ToSql};
+    /// use typed_sql::dialect::MsSql;
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     id: i64,
+    ///     score: i64
+    /// }
+    ///
+    /// let stmt = Post::table()
+    ///     .select()
+    ///     .filter(|post| post.score.descending().then(post.id.descending()).after((10, 42)))
+    ///     .order_by(|post| post.score.descending().then(post.id.descending()))
+    ///     .limit(20);
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM posts WHERE (posts.score,posts.id) < (10,42) \
+    ///     ORDER BY posts.score DESC,posts.id DESC LIMIT 20;"
+    /// );
+    ///
+    /// // Without row values, or with mixed directions, the comparison is expanded.
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<MsSql>(),
+    ///     "SELECT * FROM posts WHERE (posts.score < 10 OR posts.score = 10 AND posts.id < 42) \
+    ///     ORDER BY posts.score DESC,posts.id DESC OFFSET 0 ROWS FETCH NEXT 20 ROWS ONLY;"
+    /// );
+    ///
+    /// let stmt = Post::table()
+    ///     .select()
+    ///     .filter(|post| post.score.descending().then(post.id).after((10, 42)));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM posts WHERE (posts.score < 10 OR posts.score = 10 AND posts.id > 42);"
+    /// );
+    /// ```
+    fn after<V>(self, values: V) -> After<Self, V>
+    where
+        Self: Keyset<V>,
+    {
+        After::new(self, values)
     }
 }
 
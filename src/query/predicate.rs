@@ -1,10 +1,37 @@
-use crate::types::Primitive;
-use crate::types::{Bind, Field};
-use crate::{CheckedSql, Table};
-use std::{fmt::Write, marker::PhantomData};
+use super::select::Select;
+use crate::dialect::Dialect;
+use crate::types::{Expr, Field, Operand};
+use crate::{CheckedSql, Table, Writer};
+use std::marker::PhantomData;
+
+/// How tightly a predicate's outermost operator binds, from loosest to tightest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precedence {
+    Or,
+    And,
+    Not,
+    Comparison,
+}
 
 pub trait Predicate {
-    fn write_predicate(&self, sql: &mut String);
+    const PRECEDENCE: Precedence = Precedence::Comparison;
+
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>);
+}
+
+/// Writes `predicate`, grouping it if it binds looser than `precedence`.
+fn write_operand<P, D>(predicate: &P, precedence: Precedence, sql: &mut Writer<D>)
+where
+    P: Predicate,
+    D: Dialect,
+{
+    if P::PRECEDENCE < precedence {
+        sql.push('(');
+        predicate.write_predicate(sql);
+        sql.push(')');
+    } else {
+        predicate.write_predicate(sql);
+    }
 }
 
 pub struct And<H, T> {
@@ -17,10 +44,12 @@ where
     H: Predicate,
     T: Predicate,
 {
-    fn write_predicate(&self, sql: &mut String) {
-        self.head.write_predicate(sql);
+    const PRECEDENCE: Precedence = Precedence::And;
+
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        write_operand(&self.head, Precedence::And, sql);
         sql.push_str(" AND ");
-        self.tail.write_predicate(sql);
+        write_operand(&self.tail, Precedence::And, sql);
     }
 }
 
@@ -36,39 +65,116 @@ where
     H: Predicate,
     T: Predicate,
 {
-    fn write_predicate(&self, sql: &mut String) {
-        self.head.write_predicate(sql);
+    const PRECEDENCE: Precedence = Precedence::Or;
+
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        write_operand(&self.head, Precedence::Or, sql);
         sql.push_str(" OR ");
-        self.tail.write_predicate(sql);
+        write_operand(&self.tail, Precedence::Or, sql);
     }
 }
 
 impl<H: CheckedSql, T: CheckedSql> CheckedSql for Or<H, T> {}
 
+pub struct Not<P> {
+    pub(crate) predicate: P,
+}
+
+impl<P: Predicate> Predicate for Not<P> {
+    const PRECEDENCE: Precedence = Precedence::Not;
+
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("NOT (");
+        self.predicate.write_predicate(sql);
+        sql.push(')');
+    }
+}
+
+impl<P: CheckedSql> CheckedSql for Not<P> {}
+
+/// A predicate written in parentheses.
+pub struct Group<P> {
+    pub(crate) predicate: P,
+}
+
+impl<P: Predicate> Predicate for Group<P> {
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push('(');
+        self.predicate.write_predicate(sql);
+        sql.push(')');
+    }
+}
+
+impl<P: CheckedSql> CheckedSql for Group<P> {}
+
 pub trait Operator {
-    fn write_operator(sql: &mut String);
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>);
+
+    fn write_comparison<D, A, L, R>(sql: &mut Writer<D>, lhs: &L, rhs: &R)
+    where
+        D: Dialect,
+        L: Expr<A>,
+        R: Expr<A>,
+    {
+        lhs.write_expr(sql);
+        sql.push(' ');
+        Self::write_operator(sql);
+        sql.push(' ');
+        rhs.write_expr(sql);
+    }
 }
 
 pub struct Eq;
 
 impl Operator for Eq {
-    fn write_operator(sql: &mut String) {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
         sql.push('=');
     }
+
+    /// Comparisons with `NULL` are written as `IS NULL`, since `= NULL` is never true.
+    fn write_comparison<D, A, L, R>(sql: &mut Writer<D>, lhs: &L, rhs: &R)
+    where
+        D: Dialect,
+        L: Expr<A>,
+        R: Expr<A>,
+    {
+        lhs.write_expr(sql);
+        if rhs.is_null() {
+            sql.push_str(" IS NULL");
+        } else {
+            sql.push_str(" = ");
+            rhs.write_expr(sql);
+        }
+    }
 }
 
 pub struct Neq;
 
 impl Operator for Neq {
-    fn write_operator(sql: &mut String) {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
         sql.push_str("!=");
     }
+
+    fn write_comparison<D, A, L, R>(sql: &mut Writer<D>, lhs: &L, rhs: &R)
+    where
+        D: Dialect,
+        L: Expr<A>,
+        R: Expr<A>,
+    {
+        lhs.write_expr(sql);
+        if rhs.is_null() {
+            sql.push_str(" IS NOT NULL");
+        } else {
+            sql.push_str(" != ");
+            rhs.write_expr(sql);
+        }
+    }
 }
 
 pub struct Gt;
 
 impl Operator for Gt {
-    fn write_operator(sql: &mut String) {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
         sql.push('>');
     }
 }
@@ -76,68 +182,707 @@ impl Operator for Gt {
 pub struct Lt;
 
 impl Operator for Lt {
-    fn write_operator(sql: &mut String) {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
         sql.push('<');
     }
 }
 
-pub struct Op<T, A, U, O> {
-    lhs: Field<T, A>,
-    rhs: U,
+pub struct Ge;
+
+impl Operator for Ge {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
+        sql.push_str(">=");
+    }
+}
+
+pub struct Le;
+
+impl Operator for Le {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
+        sql.push_str("<=");
+    }
+}
+
+pub struct Like;
+
+impl Operator for Like {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
+        sql.push_str("LIKE");
+    }
+}
+
+pub struct NotLike;
+
+impl Operator for NotLike {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
+        sql.push_str("NOT LIKE");
+    }
+}
+
+pub struct ILike;
+
+impl Operator for ILike {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
+        sql.push_str(D::ILIKE);
+    }
+}
+
+pub struct IsDistinctFrom;
+
+impl Operator for IsDistinctFrom {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
+        sql.push_str("IS DISTINCT FROM");
+    }
+
+    fn write_comparison<D, A, L, R>(sql: &mut Writer<D>, lhs: &L, rhs: &R)
+    where
+        D: Dialect,
+        L: Expr<A>,
+        R: Expr<A>,
+    {
+        D::write_distinct_from(sql, lhs, rhs);
+    }
+}
+
+pub struct IsNotDistinctFrom;
+
+impl Operator for IsNotDistinctFrom {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
+        sql.push_str(D::NOT_DISTINCT_FROM);
+    }
+}
+
+/// The comparisons of an expression of type `A`, such as a column, a computed value or an aggregate.
+// The `is_*` methods build predicates, so they take the expression like the other comparisons.
+#[allow(clippy::wrong_self_convention)]
+pub trait Comparable<A>: Expr<A> + Sized {
+    /// ```
+    /// use typed_sql::{Comparable, Join, Query, Table, ToSql};
+    /// use typed_sql::query::Joined;
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i32,
+    ///     name: Option<String>
+    /// }
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     user_id: i64
+    /// }
+    ///
+    /// #[derive(Join)]
+    /// struct UserPost {
+    ///     user: User,
+    ///     post: Post
+    /// }
+    ///
+    /// let stmt = UserPost::join(|join| UserPostJoin {
+    ///     post: Joined::new(join.post.user_id.eq(join.user.id)),
+    /// })
+    /// .select()
+    /// .filter(|join| join.user.name.eq("foo"));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM users INNER JOIN posts ON posts.user_id = users.id \
+    ///     WHERE users.name = 'foo';"
+    /// );
+    /// ```
+    fn eq<U, M>(self, rhs: U) -> Compare<Self, U::Type, U, Eq>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, rhs)
+    }
+
+    /// ## `NULL`
+    /// Comparing with `None` checks for `NULL`.
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     content: Option<String>
+    /// }
+    ///
+    /// let stmt = Post::table()
+    ///     .select()
+    ///     .filter(|post| post.content.eq(None).or(post.content.neq(None::<String>)));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM posts WHERE posts.content IS NULL OR posts.content IS NOT NULL;"
+    /// );
+    /// ```
+    fn neq<U, M>(self, rhs: U) -> Compare<Self, U::Type, U, Neq>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, rhs)
+    }
+
+    fn gt<U, M>(self, rhs: U) -> Compare<Self, U::Type, U, Gt>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, rhs)
+    }
+
+    fn lt<U, M>(self, rhs: U) -> Compare<Self, U::Type, U, Lt>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, rhs)
+    }
+
+    fn ge<U, M>(self, rhs: U) -> Compare<Self, U::Type, U, Ge>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, rhs)
+    }
+
+    fn le<U, M>(self, rhs: U) -> Compare<Self, U::Type, U, Le>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, rhs)
+    }
+
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     id: i64
+    /// }
+    ///
+    /// let stmt = Post::table().select().filter(|post| post.id.between(2, 5));
+    ///
+    /// assert_eq!(stmt.to_sql(), "SELECT * FROM posts WHERE posts.id BETWEEN 2 AND 5;");
+    /// ```
+    fn between<L, H>(self, low: L, high: H) -> Between<Self, A, L, H>
+    where
+        L: Expr<A>,
+        H: Expr<A>,
+    {
+        Between::new(self, low, high)
+    }
+
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    /// use typed_sql::types::computed::{coalesce, length, lower};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     name: String,
+    ///     nickname: Option<String>,
+    ///     score: i64,
+    ///     bonus: i64
+    /// }
+    ///
+    /// let stmt = User::table().select().filter(|user| {
+    ///     lower(user.name)
+    ///         .like("a%")
+    ///         .and(length(user.name).between(2, 10))
+    ///         .and((user.score + user.bonus).in_list(vec![1, 2]))
+    ///         .and(lower(user.nickname).is_null())
+    /// });
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM users WHERE LOWER(users.name) LIKE 'a%' \
+    ///     AND LENGTH(users.name) BETWEEN 2 AND 10 \
+    ///     AND users.score + users.bonus IN (1,2) \
+    ///     AND LOWER(users.nickname) IS NULL;"
+    /// );
+    ///
+    /// let stmt = User::table()
+    ///     .select()
+    ///     .filter(|user| coalesce(user.nickname, user.name).not_in(vec!["admin"]));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM users WHERE COALESCE(users.nickname, users.name) NOT IN ('admin');"
+    /// );
+    /// ```
+    fn like<U, M>(self, pattern: U) -> Compare<Self, U::Type, U, Like>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, pattern)
+    }
+
+    fn not_like<U, M>(self, pattern: U) -> Compare<Self, U::Type, U, NotLike>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, pattern)
+    }
+
+    /// Case-insensitive `LIKE`, see [`Dialect::ILIKE`](crate::dialect::Dialect::ILIKE).
+    ///
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    /// use typed_sql::dialect::MySql;
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     content: String
+    /// }
+    ///
+    /// let stmt = Post::table().select().filter(|post| post.content.ilike("%rust%"));
+    ///
+    /// assert_eq!(stmt.to_sql(), "SELECT * FROM posts WHERE posts.content ILIKE '%rust%';");
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<MySql>(),
+    ///     "SELECT * FROM posts WHERE posts.content LIKE '%rust%';"
+    /// );
+    /// ```
+    fn ilike<U, M>(self, pattern: U) -> Compare<Self, U::Type, U, ILike>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, pattern)
+    }
+
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     id: i64
+    /// }
+    ///
+    /// let stmt = Post::table().select().filter(|post| post.id.in_list(vec![1, 2, 3]));
+    ///
+    /// assert_eq!(stmt.to_sql(), "SELECT * FROM posts WHERE posts.id IN (1,2,3);");
+    /// ```
+    /// ## Empty lists
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     id: i64
+    /// }
+    ///
+    /// let stmt = Post::table().select().filter(|post| post.id.in_list(Vec::<i64>::new()));
+    ///
+    /// assert_eq!(stmt.to_sql(), "SELECT * FROM posts WHERE 1 = 0;");
+    /// ```
+    fn in_list<I>(self, values: I) -> InList<Self, A, I, In>
+    where
+        I: IntoIterator + Clone,
+        I::Item: Expr<A>,
+    {
+        InList::new(self, values)
+    }
+
+    fn not_in<I>(self, values: I) -> InList<Self, A, I, NotIn>
+    where
+        I: IntoIterator + Clone,
+        I::Item: Expr<A>,
+    {
+        InList::new(self, values)
+    }
+
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     content: Option<String>
+    /// }
+    ///
+    /// let stmt = Post::table().select().filter(|post| post.content.is_null());
+    ///
+    /// assert_eq!(stmt.to_sql(), "SELECT * FROM posts WHERE posts.content IS NULL;");
+    /// ```
+    fn is_null(self) -> IsNull<Self, A> {
+        IsNull::new(self)
+    }
+
+    fn is_not_null(self) -> IsNotNull<Self, A> {
+        IsNotNull::new(self)
+    }
+
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    /// use typed_sql::dialect::MySql;
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     content: Option<String>
+    /// }
+    ///
+    /// let stmt = Post::table()
+    ///     .select()
+    ///     .filter(|post| post.content.is_distinct_from("foo"));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM posts WHERE posts.content IS DISTINCT FROM 'foo';"
+    /// );
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<MySql>(),
+    ///     "SELECT * FROM posts WHERE NOT (posts.content <=> 'foo');"
+    /// );
+    /// ```
+    fn is_distinct_from<U, M>(self, rhs: U) -> Compare<Self, U::Type, U, IsDistinctFrom>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, rhs)
+    }
+
+    /// Null-safe equality, see [`Dialect::NOT_DISTINCT_FROM`](crate::dialect::Dialect::NOT_DISTINCT_FROM).
+    ///
+    /// ```
+    /// use typed_sql::{Binding, Comparable, Query, Table, ToSql};
+    /// use typed_sql::dialect::MySql;
+    ///
+    /// #[derive(Binding, Table)]
+    /// struct Post {
+    ///     content: Option<String>
+    /// }
+    ///
+    /// let plan = Post::prepare("contentplan", |binds| {
+    ///     Post::table()
+    ///         .select()
+    ///         .filter(|post| post.content.is_not_distinct_from(binds.content))
+    /// });
+    ///
+    /// assert_eq!(
+    ///     plan.to_sql(),
+    ///     "PREPARE contentplan AS SELECT * FROM posts WHERE posts.content IS NOT DISTINCT FROM $1;"
+    /// );
+    /// assert_eq!(
+    ///     plan.to_sql_for::<MySql>(),
+    ///     "PREPARE contentplan FROM 'SELECT * FROM posts WHERE posts.content <=> ?';"
+    /// );
+    /// ```
+    fn is_not_distinct_from<U, M>(self, rhs: U) -> Compare<Self, U::Type, U, IsNotDistinctFrom>
+    where
+        U: Operand<A, M>,
+        Self: Expr<U::Type>,
+    {
+        Compare::new(self, rhs)
+    }
+}
+
+/// A comparison of two expressions of type `A`.
+pub struct Compare<L, A, U, O> {
+    pub(crate) lhs: L,
+    pub(crate) rhs: U,
+    _type: PhantomData<A>,
     _operator: PhantomData<O>,
 }
 
-impl<T, A, U, O> Op<T, A, U, O> {
-    pub(crate) fn new(lhs: Field<T, A>, rhs: U) -> Self {
+impl<L, A, U, O> Compare<L, A, U, O> {
+    pub(crate) fn new(lhs: L, rhs: U) -> Self {
         Self {
             lhs,
             rhs,
+            _type: PhantomData,
             _operator: PhantomData,
         }
     }
 }
 
-impl<T, A, U, O> Predicate for Op<T, A, U, O>
+impl<L, A, U, O> Predicate for Compare<L, A, U, O>
 where
-    T: Table,
-    U: Primitive,
+    L: Expr<A>,
+    U: Expr<A>,
     O: Operator,
 {
-    fn write_predicate(&self, sql: &mut String) {
-        self.lhs.write_field(sql);
-        sql.push(' ');
-        O::write_operator(sql);
-        sql.push(' ');
-        self.rhs.write_primative(sql);
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        O::write_comparison(sql, &self.lhs, &self.rhs);
     }
 }
 
-impl<T, T2, A, O> Predicate for Op<T, A, Field<T2, A>, O>
+impl<L: CheckedSql, A, U: CheckedSql, O> CheckedSql for Compare<L, A, U, O> {}
+
+/// A range check of an expression of type `A`.
+pub struct Between<E, A, L, H> {
+    expr: E,
+    low: L,
+    high: H,
+    _type: PhantomData<A>,
+}
+
+impl<E, A, L, H> Between<E, A, L, H> {
+    pub(crate) fn new(expr: E, low: L, high: H) -> Self {
+        Self {
+            expr,
+            low,
+            high,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<E, A, L, H> Predicate for Between<E, A, L, H>
 where
-    T: Table,
-    T2: Table,
-    O: Operator,
+    E: Expr<A>,
+    L: Expr<A>,
+    H: Expr<A>,
 {
-    fn write_predicate(&self, sql: &mut String) {
-        self.lhs.write_field(sql);
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.expr.write_expr(sql);
+        sql.push_str(" BETWEEN ");
+        self.low.write_expr(sql);
+        sql.push_str(" AND ");
+        self.high.write_expr(sql);
+    }
+}
+
+impl<E: CheckedSql, A, L: CheckedSql, H: CheckedSql> CheckedSql for Between<E, A, L, H> {}
+
+pub trait ListOperator: Operator {
+    /// The predicate written in place of a comparison with an empty list.
+    const EMPTY: &'static str;
+}
+
+pub struct In;
+
+impl Operator for In {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
+        sql.push_str("IN");
+    }
+}
+
+impl ListOperator for In {
+    const EMPTY: &'static str = "1 = 0";
+}
+
+pub struct NotIn;
+
+impl Operator for NotIn {
+    fn write_operator<D: Dialect>(sql: &mut Writer<D>) {
+        sql.push_str("NOT IN");
+    }
+}
+
+impl ListOperator for NotIn {
+    const EMPTY: &'static str = "1 = 1";
+}
+
+/// A check of an expression of type `A` against a list of values.
+pub struct InList<E, A, I, O> {
+    expr: E,
+    values: I,
+    _type: PhantomData<A>,
+    _operator: PhantomData<O>,
+}
+
+impl<E, A, I, O> InList<E, A, I, O> {
+    pub(crate) fn new(expr: E, values: I) -> Self {
+        Self {
+            expr,
+            values,
+            _type: PhantomData,
+            _operator: PhantomData,
+        }
+    }
+}
+
+impl<E, A, I, O> Predicate for InList<E, A, I, O>
+where
+    E: Expr<A>,
+    I: IntoIterator + Clone,
+    I::Item: Expr<A>,
+    O: ListOperator,
+{
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        let mut values = self.values.clone().into_iter().peekable();
+        if values.peek().is_none() {
+            sql.push_str(O::EMPTY);
+            return;
+        }
+
+        self.expr.write_expr(sql);
         sql.push(' ');
         O::write_operator(sql);
-        sql.push(' ');
-        self.rhs.write_field(sql);
+        sql.push_str(" (");
+        while let Some(value) = values.next() {
+            value.write_expr(sql);
+            if values.peek().is_some() {
+                sql.push(',');
+            }
+        }
+        sql.push(')');
+    }
+}
+
+impl<E, A, I, O> CheckedSql for InList<E, A, I, O>
+where
+    E: CheckedSql,
+    I: IntoIterator,
+    I::Item: CheckedSql,
+{
+}
+
+pub struct InSelect<T, A, S, O> {
+    field: Field<T, A>,
+    select: S,
+    _operator: PhantomData<O>,
+}
+
+impl<T, A, S, O> InSelect<T, A, S, O> {
+    pub(crate) fn new(field: Field<T, A>, select: S) -> Self {
+        Self {
+            field,
+            select,
+            _operator: PhantomData,
+        }
     }
 }
 
-impl<T, A, O> Predicate for Op<T, A, Bind, O>
+impl<T, A, S, O> Predicate for InSelect<T, A, S, O>
 where
     T: Table,
-    O: Operator,
+    S: Select,
+    O: ListOperator,
 {
-    fn write_predicate(&self, sql: &mut String) {
-        self.lhs.write_field(sql);
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.field.write_field(sql);
         sql.push(' ');
         O::write_operator(sql);
-        sql.write_fmt(format_args!(" ${}", self.rhs.n)).unwrap();
+        sql.push_str(" (");
+        self.select.write_sql_unchecked(sql);
+        sql.push(')');
+    }
+}
+
+impl<T, A, S: CheckedSql, O> CheckedSql for InSelect<T, A, S, O> {}
+
+/// Checks if `select` returns any rows.
+///
+/// ```
+/// use typed_sql::{Comparable, Query, Table, ToSql};
+/// use typed_sql::query::{exists, not_exists};
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Post {
+///     user_id: i64
+/// }
+///
+/// let stmt = User::table().select().filter(|user| {
+///     exists(Post::table().select().filter(|post| post.user_id.eq(user.id)))
+/// });
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "SELECT * FROM users WHERE EXISTS (SELECT * FROM posts WHERE posts.user_id = users.id);"
+/// );
+///
+/// let stmt = User::table().delete().filter(|user| {
+///     not_exists(Post::table().select().filter(|post| post.user_id.eq(user.id)))
+/// });
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "DELETE FROM users WHERE NOT EXISTS (SELECT * FROM posts WHERE posts.user_id = users.id);"
+/// );
+/// ```
+pub fn exists<S: Select>(select: S) -> Exists<S> {
+    Exists { select }
+}
+
+/// Checks if `select` returns no rows.
+pub fn not_exists<S: Select>(select: S) -> NotExists<S> {
+    NotExists { select }
+}
+
+pub struct Exists<S> {
+    select: S,
+}
+
+impl<S: Select> Predicate for Exists<S> {
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("EXISTS (");
+        self.select.write_sql_unchecked(sql);
+        sql.push(')');
+    }
+}
+
+impl<S: CheckedSql> CheckedSql for Exists<S> {}
+
+pub struct NotExists<S> {
+    select: S,
+}
+
+impl<S: Select> Predicate for NotExists<S> {
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("NOT EXISTS (");
+        self.select.write_sql_unchecked(sql);
+        sql.push(')');
+    }
+}
+
+impl<S: CheckedSql> CheckedSql for NotExists<S> {}
+
+/// A check of whether an expression of type `A` is `NULL`.
+pub struct IsNull<E, A> {
+    expr: E,
+    _type: PhantomData<A>,
+}
+
+impl<E, A> IsNull<E, A> {
+    pub(crate) fn new(expr: E) -> Self {
+        Self {
+            expr,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<E: Expr<A>, A> Predicate for IsNull<E, A> {
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.expr.write_expr(sql);
+        sql.push_str(" IS NULL");
+    }
+}
+
+impl<E: CheckedSql, A> CheckedSql for IsNull<E, A> {}
+
+/// A check of whether an expression of type `A` isn't `NULL`.
+pub struct IsNotNull<E, A> {
+    expr: E,
+    _type: PhantomData<A>,
+}
+
+impl<E, A> IsNotNull<E, A> {
+    pub(crate) fn new(expr: E) -> Self {
+        Self {
+            expr,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<E: Expr<A>, A> Predicate for IsNotNull<E, A> {
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.expr.write_expr(sql);
+        sql.push_str(" IS NOT NULL");
     }
 }
 
-impl<T, A, U: CheckedSql, O> CheckedSql for Op<T, A, U, O> {}
+impl<E: CheckedSql, A> CheckedSql for IsNotNull<E, A> {}
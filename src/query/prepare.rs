@@ -1,4 +1,5 @@
-use crate::{Binding, CheckedSql, ToSql};
+use crate::dialect::Dialect;
+use crate::{Binding, CheckedSql, ToSql, Writer};
 use std::marker::PhantomData;
 
 pub struct Prepare<'a, B, S> {
@@ -18,7 +19,7 @@ impl<'a, B, S> Prepare<'a, B, S> {
 }
 
 impl<B: Binding, S: ToSql> Prepare<'_, B, S> {
-    pub fn execute(&self, binding: B) -> Execute<B> {
+    pub fn execute(&self, binding: B) -> Execute<'_, B> {
         Execute {
             name: self.name,
             binding,
@@ -27,19 +28,26 @@ impl<B: Binding, S: ToSql> Prepare<'_, B, S> {
 }
 
 impl<B: Binding, S: ToSql> ToSql for Prepare<'_, B, S> {
-    fn write_sql_unchecked(&self, sql: &mut String) {
-        sql.push_str("PREPARE ");
-        sql.push_str(self.name);
+    /// Values are always written inline, since a `PREPARE` can't have parameters of its own.
+    ///
+    /// This fails to compile for dialects without `PREPARE`.
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
+        let () = AssertPrepare::<D>::OK;
 
-        B::write_types(sql);
-
-        sql.push_str(" AS ");
-        self.stmt.write_sql_unchecked(sql);
+        sql.write_inline(|sql| D::write_prepare::<B, S>(sql, self.name, &self.stmt));
     }
 }
 
 impl<B, S: CheckedSql> CheckedSql for Prepare<'_, B, S> {}
 
+/// Fails to compile a prepared statement for a dialect without `PREPARE`,
+/// such as SQLite or SQL Server.
+struct AssertPrepare<D>(PhantomData<D>);
+
+impl<D: Dialect> AssertPrepare<D> {
+    const OK: () = assert!(D::PREPARE, "this dialect can't prepare statements by name");
+}
+
 impl<B, S: Copy> Clone for Prepare<'_, B, S> {
     fn clone(&self) -> Self {
         *self
@@ -55,12 +63,11 @@ pub struct Execute<'a, B> {
 }
 
 impl<B: Binding> ToSql for Execute<'_, B> {
-    fn write_sql_unchecked(&self, sql: &mut String) {
-        sql.push_str("EXECUTE ");
-        sql.push_str(self.name);
-        sql.push('(');
-        self.binding.write_values(sql);
-        sql.push(')');
+    /// This fails to compile for dialects without `PREPARE`.
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
+        let () = AssertPrepare::<D>::OK;
+
+        D::write_execute(sql, self.name, &self.binding);
     }
 }
 
@@ -0,0 +1,176 @@
+use super::delete::Delete;
+use super::filter::Filter;
+use super::insert::{InsertRows, InsertStatement};
+use super::select::WriteQueryable;
+use super::update::{Update, UpdateSet};
+use super::upsert::{ConflictAction, ConflictTarget, Upsert};
+use super::Predicate;
+use crate::dialect::Dialect;
+use crate::types::Field;
+use crate::{CheckedSql, Table, ToSql, Writer};
+use std::marker::PhantomData;
+
+/// A statement that changes rows of `Table`, which can return the rows it changed.
+pub trait Returnable: ToSql {
+    type Table: Table + ?Sized;
+
+    /// Writes this statement followed by the `columns` of the changed rows.
+    ///
+    /// This fails to compile for dialects without `RETURNING`.
+    fn write_returning<D: Dialect, P: WriteQueryable>(&self, columns: &P, sql: &mut Writer<D>) {
+        let () = AssertReturning::<D>::OK;
+
+        self.write_sql_unchecked(sql);
+        sql.push_str(" RETURNING ");
+        columns.write_query(sql);
+    }
+}
+
+/// Fails to compile a statement that returns rows for a dialect without `RETURNING`,
+/// such as MySQL or SQL Server.
+struct AssertReturning<D>(PhantomData<D>);
+
+impl<D: Dialect> AssertReturning<D> {
+    const OK: () = assert!(
+        D::RETURNING,
+        "this dialect can't return the rows changed by a statement"
+    );
+}
+
+impl<T, I> Returnable for InsertStatement<T, I>
+where
+    T: Table + ?Sized,
+    InsertStatement<T, I>: ToSql,
+{
+    type Table = T;
+}
+
+impl<T, V, C, A> Returnable for Upsert<T, V, C, A>
+where
+    T: Table,
+    V: InsertRows,
+    C: ConflictTarget,
+    A: ConflictAction,
+{
+    type Table = T;
+}
+
+impl<T, S> Returnable for Update<T, S>
+where
+    T: Table + ?Sized,
+    S: UpdateSet,
+{
+    type Table = T;
+}
+
+impl<T: Table + ?Sized> Returnable for Delete<T> {
+    type Table = T;
+}
+
+impl<S, P> Returnable for Filter<S, P>
+where
+    S: Returnable,
+    P: Predicate,
+{
+    type Table = S::Table;
+}
+
+/// A statement that returns the `columns` of the rows it changed.
+pub struct Returning<S, P> {
+    stmt: S,
+    columns: P,
+}
+
+impl<S, P> Returning<S, P> {
+    pub(crate) fn new(stmt: S, columns: P) -> Self {
+        Self { stmt, columns }
+    }
+}
+
+impl<S, P> ToSql for Returning<S, P>
+where
+    S: Returnable,
+    P: WriteQueryable,
+{
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.stmt.write_returning(&self.columns, sql);
+    }
+}
+
+impl<S: CheckedSql, P> CheckedSql for Returning<S, P> {}
+
+/// The integer column of an id generated by the database, such as an auto-increment key.
+pub trait GeneratedId {}
+
+impl GeneratedId for i64 {}
+
+impl GeneratedId for Option<i64> {}
+
+impl<T: Table, V: InsertRows> InsertStatement<T, V> {
+    /// Returns the `id` generated for the inserted row.
+    ///
+    /// Dialects without `RETURNING` can only read the last generated id,
+    /// which is selected by a second statement on the same connection with
+    /// [`fetch_id`](ReturningId::fetch_id), so only one row should be inserted.
+    /// ```
+    /// use typed_sql::{Insertable, Query, Table, ToSql};
+    /// use typed_sql::dialect::MySql;
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String
+    /// }
+    ///
+    /// #[derive(Insertable)]
+    /// struct NewUser {
+    ///     name: String
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .insert(NewUser { name: String::from("Ann") })
+    ///     .returning_id(|user| user.id);
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "INSERT INTO users(name) VALUES ('Ann') RETURNING users.id;"
+    /// );
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<MySql>(),
+    ///     "INSERT INTO users(name) VALUES ('Ann');"
+    /// );
+    /// ```
+    pub fn returning_id<F, A>(self, f: F) -> ReturningId<Self, Field<T, A>>
+    where
+        F: FnOnce(T::Fields) -> Field<T, A>,
+        A: GeneratedId,
+    {
+        ReturningId {
+            stmt: self,
+            id: f(Default::default()),
+        }
+    }
+}
+
+/// An insert that returns the id generated for the inserted row.
+pub struct ReturningId<S, F> {
+    stmt: S,
+    id: F,
+}
+
+impl<S, T, A> ToSql for ReturningId<S, Field<T, A>>
+where
+    S: ToSql,
+    T: Table,
+{
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.stmt.write_sql_unchecked(sql);
+        if D::RETURNING {
+            sql.push_str(" RETURNING ");
+            self.id.write_field(sql);
+        }
+    }
+}
+
+impl<S: CheckedSql, F> CheckedSql for ReturningId<S, F> {}
@@ -0,0 +1,489 @@
+//! Aggregate functions over the rows of a group.
+//!
+//! Aggregates can be selected with [`Query::aggregate`](crate::Query::aggregate)
+//! and compared in [`having`](super::GroupBy::having).
+//! ```
+//! use typed_sql::{Comparable, Query, Table, ToSql};
+//! use typed_sql::query::{avg, max, string_agg, sum};
+//! use typed_sql::dialect::MySql;
+//!
+//! #[derive(Table)]
+//! struct Order {
+//!     customer_id: i64,
+//!     amount: i64,
+//!     note: String
+//! }
+//!
+//! let stmt = Order::table().aggregate(|order| avg(order.amount));
+//!
+//! assert_eq!(
+//!     stmt.to_sql(),
+//!     "SELECT CAST(AVG(orders.amount) AS DOUBLE PRECISION) FROM orders;"
+//! );
+//!
+//! let stmt = Order::table().aggregate(|order| sum(order.amount));
+//!
+//! assert_eq!(
+//!     stmt.to_sql_for::<MySql>(),
+//!     "SELECT CAST(SUM(orders.amount) AS SIGNED) FROM orders;"
+//! );
+//!
+//! let stmt = Order::table()
+//!     .aggregate(|order| string_agg(order.note, ", "))
+//!     .group_by(|order| order.customer_id)
+//!     .having(|order| max(order.amount).gt(100));
+//!
+//! assert_eq!(
+//!     stmt.to_sql(),
+//!     "SELECT STRING_AGG(orders.note, ', ') FROM orders \
+//!     GROUP BY orders.customer_id HAVING MAX(orders.amount) > 100;"
+//! );
+//! assert_eq!(
+//!     stmt.to_sql_for::<MySql>(),
+//!     "SELECT GROUP_CONCAT(orders.note SEPARATOR ', ') FROM orders \
+//!     GROUP BY orders.customer_id HAVING MAX(orders.amount) > 100;"
+//! );
+//! ```
+
+use super::{Projection, Scalar, WildCard, WriteQueryable};
+use crate::dialect::Dialect;
+use crate::query::predicate::Comparable;
+use crate::types::computed::{Boolean, CastType, Castable, Numeric, Text};
+use crate::types::{Expr, Nullable, TypedExpr};
+use crate::{CheckedSql, Writer};
+use std::marker::PhantomData;
+
+pub trait AggregateFunction<A> {
+    const NAME: &'static str;
+
+    /// The type of the aggregated value.
+    type Output;
+
+    /// The type the aggregate is cast to, if databases can return a wider type than `Output`.
+    const CAST_TYPE: Option<CastType> = None;
+
+    fn write_aggregate<D: Dialect, E: Expr<A>>(&self, expr: &E, sql: &mut Writer<D>) {
+        sql.push_str(Self::NAME);
+        sql.push('(');
+        expr.write_expr(sql);
+        sql.push(')');
+    }
+}
+
+pub struct CountAgg;
+
+impl<A> AggregateFunction<A> for CountAgg {
+    const NAME: &'static str = "COUNT";
+
+    type Output = i64;
+}
+
+/// A numeric type that can be summed.
+///
+/// Integers are summed as `i64`, so sums of small or unsigned columns don't overflow
+/// (`u64` sums past `i64::MAX` still don't fit).
+pub trait Summable: Numeric {
+    /// The type of the sum.
+    type Sum: Nullable + Castable;
+}
+
+macro_rules! impl_summable {
+    ($($ty:ty => $sum:ty),*) => {
+        $(
+            impl Summable for $ty {
+                type Sum = $sum;
+            }
+        )*
+    };
+}
+
+impl_summable!(
+    i8 => i64,
+    i16 => i64,
+    i32 => i64,
+    i64 => i64,
+    isize => i64,
+    u8 => i64,
+    u16 => i64,
+    u32 => i64,
+    u64 => i64,
+    usize => i64,
+    f32 => f32,
+    f64 => f64
+);
+
+impl<N: Summable> Summable for Option<N> {
+    type Sum = N::Sum;
+}
+
+/// The sum of a column, which is `NULL` if there are no rows.
+///
+/// The sum is cast to the [`Summable::Sum`] of the column,
+/// since PostgreSQL and MySQL sum integers as `NUMERIC` or `DECIMAL`.
+pub struct Sum;
+
+impl<A: Summable> AggregateFunction<A> for Sum {
+    const NAME: &'static str = "SUM";
+
+    type Output = <A::Sum as Nullable>::Output;
+
+    const CAST_TYPE: Option<CastType> = Some(<A::Sum as Castable>::CAST_TYPE);
+}
+
+/// The average of a column, cast to a double since PostgreSQL and MySQL average integers
+/// as `NUMERIC` or `DECIMAL`.
+pub struct Avg;
+
+impl<A: Numeric> AggregateFunction<A> for Avg {
+    const NAME: &'static str = "AVG";
+
+    type Output = Option<f64>;
+
+    const CAST_TYPE: Option<CastType> = Some(CastType::Double);
+}
+
+pub struct Min;
+
+impl<A: Nullable> AggregateFunction<A> for Min {
+    const NAME: &'static str = "MIN";
+
+    type Output = A::Output;
+}
+
+pub struct Max;
+
+impl<A: Nullable> AggregateFunction<A> for Max {
+    const NAME: &'static str = "MAX";
+
+    type Output = A::Output;
+}
+
+/// Concatenates strings with a separator.
+pub struct StringAgg<'a> {
+    separator: &'a str,
+}
+
+impl<A: Text> AggregateFunction<A> for StringAgg<'_> {
+    const NAME: &'static str = "STRING_AGG";
+
+    type Output = Option<String>;
+
+    fn write_aggregate<D: Dialect, E: Expr<A>>(&self, expr: &E, sql: &mut Writer<D>) {
+        D::write_string_agg(sql, expr, self.separator);
+    }
+}
+
+/// Collects values into an array (PostgreSQL only).
+pub struct ArrayAgg;
+
+impl<A> AggregateFunction<A> for ArrayAgg {
+    const NAME: &'static str = "ARRAY_AGG";
+
+    type Output = Option<Vec<A>>;
+
+    /// This fails to compile for dialects without arrays.
+    fn write_aggregate<D: Dialect, E: Expr<A>>(&self, expr: &E, sql: &mut Writer<D>) {
+        let () = AssertArrayAgg::<D>::OK;
+
+        sql.push_str(<Self as AggregateFunction<A>>::NAME);
+        sql.push('(');
+        expr.write_expr(sql);
+        sql.push(')');
+    }
+}
+
+/// Fails to compile an `ARRAY_AGG` for a dialect without arrays, such as MySQL or SQLite.
+struct AssertArrayAgg<D>(PhantomData<D>);
+
+impl<D: Dialect> AssertArrayAgg<D> {
+    const OK: () = assert!(
+        D::ARRAY_AGG,
+        "this dialect can't collect values into an array"
+    );
+}
+
+pub struct BoolAnd;
+
+impl<A: Boolean> AggregateFunction<A> for BoolAnd {
+    const NAME: &'static str = "BOOL_AND";
+
+    type Output = Option<bool>;
+
+    fn write_aggregate<D: Dialect, E: Expr<A>>(&self, expr: &E, sql: &mut Writer<D>) {
+        D::write_bool_agg(sql, D::BOOL_AND, expr);
+    }
+}
+
+pub struct BoolOr;
+
+impl<A: Boolean> AggregateFunction<A> for BoolOr {
+    const NAME: &'static str = "BOOL_OR";
+
+    type Output = Option<bool>;
+
+    fn write_aggregate<D: Dialect, E: Expr<A>>(&self, expr: &E, sql: &mut Writer<D>) {
+        D::write_bool_agg(sql, D::BOOL_OR, expr);
+    }
+}
+
+/// An aggregate function `F` over the expression `E` of type `A`.
+pub struct Aggregate<F, E, A> {
+    function: F,
+    expr: E,
+    _type: PhantomData<A>,
+}
+
+impl<F, E, A> Aggregate<F, E, A> {
+    pub(crate) fn new(function: F, expr: E) -> Self {
+        Self {
+            function,
+            expr,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<F, E, A> Aggregate<F, E, A>
+where
+    F: AggregateFunction<A>,
+    E: Expr<A>,
+{
+    /// Writes the aggregate cast to its output type, after the `OVER` of a window from `write_over`.
+    pub(crate) fn write_cast<D, W>(&self, sql: &mut Writer<D>, write_over: W)
+    where
+        D: Dialect,
+        W: FnOnce(&mut Writer<D>),
+    {
+        if let Some(ty) = F::CAST_TYPE {
+            sql.push_str("CAST(");
+            self.function.write_aggregate(&self.expr, sql);
+            write_over(sql);
+            sql.push_str(" AS ");
+            D::write_cast_type(sql, ty);
+            sql.push(')');
+        } else {
+            self.function.write_aggregate(&self.expr, sql);
+            write_over(sql);
+        }
+    }
+}
+
+impl<F, E, A> Comparable<F::Output> for Aggregate<F, E, A>
+where
+    F: AggregateFunction<A>,
+    E: Expr<A>,
+{
+}
+
+impl<F, E, A> Expr<F::Output> for Aggregate<F, E, A>
+where
+    F: AggregateFunction<A>,
+    E: Expr<A>,
+{
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_cast(sql, |_| {});
+    }
+}
+
+impl<F, E, A> TypedExpr for Aggregate<F, E, A>
+where
+    F: AggregateFunction<A>,
+    E: Expr<A>,
+{
+    type Type = F::Output;
+}
+
+impl<F, E, A> WriteQueryable for Aggregate<F, E, A>
+where
+    F: AggregateFunction<A>,
+    E: Expr<A>,
+{
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_cast(sql, |_| {});
+    }
+}
+
+impl<F, E, A> Projection for Aggregate<F, E, A>
+where
+    F: AggregateFunction<A>,
+    E: Expr<A>,
+{
+    type Output = F::Output;
+}
+
+impl<F, E, A> Scalar for Aggregate<F, E, A>
+where
+    F: AggregateFunction<A>,
+    E: Expr<A>,
+{
+}
+
+impl<F, E: CheckedSql, A> CheckedSql for Aggregate<F, E, A> {}
+
+/// The wildcard is only an expression of its own type, for `COUNT(*)`.
+impl Expr<WildCard> for WildCard {
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push('*');
+    }
+}
+
+impl CheckedSql for WildCard {}
+
+/// Counts the rows where `expr` isn't `NULL`.
+pub fn count<E: TypedExpr>(expr: E) -> Aggregate<CountAgg, E, E::Type> {
+    Aggregate::new(CountAgg, expr)
+}
+
+/// Counts every row.
+pub fn count_all() -> Aggregate<CountAgg, WildCard, WildCard> {
+    Aggregate::new(CountAgg, WildCard)
+}
+
+/// Sums the values of `expr`, skipping `NULL`s.
+///
+/// Integer columns are summed as `i64`.
+/// ```
+/// use typed_sql::{Query, Table, ToSql};
+/// use typed_sql::query::sum;
+///
+/// #[derive(Table)]
+/// struct Order {
+///     quantity: u32,
+/// }
+///
+/// let stmt = Order::table().aggregate(|order| sum(order.quantity));
+///
+/// assert_eq!(stmt.to_sql(), "SELECT CAST(SUM(orders.quantity) AS BIGINT) FROM orders;");
+/// ```
+///
+/// Only numbers can be summed.
+/// ```compile_fail
+/// use typed_sql::{Query, Table};
+/// use typed_sql::query::sum;
+///
+/// #[derive(Table)]
+/// struct User {
+///     ok: bool,
+/// }
+///
+/// User::table().aggregate(|user| sum(user.ok));
+/// ```
+pub fn sum<E>(expr: E) -> Aggregate<Sum, E, E::Type>
+where
+    E: TypedExpr,
+    E::Type: Summable,
+{
+    Aggregate::new(Sum, expr)
+}
+
+/// Averages the values of `expr`, skipping `NULL`s.
+///
+/// Only numbers can be averaged.
+/// ```compile_fail
+/// use typed_sql::{Query, Table};
+/// use typed_sql::query::avg;
+///
+/// #[derive(Table)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// User::table().aggregate(|user| avg(user.name));
+/// ```
+pub fn avg<E>(expr: E) -> Aggregate<Avg, E, E::Type>
+where
+    E: TypedExpr,
+    E::Type: Numeric,
+{
+    Aggregate::new(Avg, expr)
+}
+
+pub fn min<E>(expr: E) -> Aggregate<Min, E, E::Type>
+where
+    E: TypedExpr,
+    E::Type: Nullable,
+{
+    Aggregate::new(Min, expr)
+}
+
+pub fn max<E>(expr: E) -> Aggregate<Max, E, E::Type>
+where
+    E: TypedExpr,
+    E::Type: Nullable,
+{
+    Aggregate::new(Max, expr)
+}
+
+/// Concatenates the values of `expr` separated by `separator`,
+/// written as `GROUP_CONCAT` in MySQL and SQLite.
+/// ```
+/// use typed_sql::{Query, Table, ToSql};
+/// use typed_sql::query::string_agg;
+///
+/// #[derive(Table)]
+/// struct User {
+///     nickname: Option<String>,
+/// }
+///
+/// let stmt = User::table().aggregate(|user| string_agg(user.nickname, ", "));
+///
+/// assert_eq!(stmt.to_sql(), "SELECT STRING_AGG(users.nickname, ', ') FROM users;");
+/// ```
+#[doc(alias = "group_concat")]
+pub fn string_agg<E>(expr: E, separator: &str) -> Aggregate<StringAgg<'_>, E, E::Type>
+where
+    E: TypedExpr,
+    E::Type: Text,
+{
+    Aggregate::new(StringAgg { separator }, expr)
+}
+
+/// Collects the values of `expr` into an array (PostgreSQL only).
+/// ```
+/// use typed_sql::{Query, Table, ToSql};
+/// use typed_sql::query::array_agg;
+///
+/// #[derive(Table)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// let stmt = User::table().aggregate(|user| array_agg(user.name));
+///
+/// assert_eq!(stmt.to_sql(), "SELECT ARRAY_AGG(users.name) FROM users;");
+/// ```
+///
+/// This fails to compile for other dialects.
+/// ```compile_fail
+/// use typed_sql::{Query, Table, ToSql};
+/// use typed_sql::dialect::MySql;
+/// use typed_sql::query::array_agg;
+///
+/// #[derive(Table)]
+/// struct User {
+///     name: String,
+/// }
+///
+/// User::table().aggregate(|user| array_agg(user.name)).to_sql_for::<MySql>();
+/// ```
+pub fn array_agg<E: TypedExpr>(expr: E) -> Aggregate<ArrayAgg, E, E::Type> {
+    Aggregate::new(ArrayAgg, expr)
+}
+
+/// Checks if every value of `expr` is true, written as `MIN` in MySQL, SQLite and SQL Server.
+pub fn bool_and<E>(expr: E) -> Aggregate<BoolAnd, E, E::Type>
+where
+    E: TypedExpr,
+    E::Type: Boolean,
+{
+    Aggregate::new(BoolAnd, expr)
+}
+
+/// Checks if any value of `expr` is true, written as `MAX` in MySQL, SQLite and SQL Server.
+pub fn bool_or<E>(expr: E) -> Aggregate<BoolOr, E, E::Type>
+where
+    E: TypedExpr,
+    E::Type: Boolean,
+{
+    Aggregate::new(BoolOr, expr)
+}
@@ -0,0 +1,212 @@
+use super::queryable::Rows;
+use super::{Order, Select, Selectable};
+use crate::dialect::Dialect;
+use crate::{CheckedSql, ToSql, Writer};
+use std::marker::PhantomData;
+
+pub trait SetOperator {
+    const OPERATOR: &'static str;
+}
+
+pub struct Union;
+
+impl SetOperator for Union {
+    const OPERATOR: &'static str = "UNION";
+}
+
+pub struct UnionAll;
+
+impl SetOperator for UnionAll {
+    const OPERATOR: &'static str = "UNION ALL";
+}
+
+pub struct Intersect;
+
+impl SetOperator for Intersect {
+    const OPERATOR: &'static str = "INTERSECT";
+}
+
+pub struct Except;
+
+impl SetOperator for Except {
+    const OPERATOR: &'static str = "EXCEPT";
+}
+
+/// The rows of two selects combined by the operator `O`.
+///
+/// Operands that are compound selects or have their own `ORDER BY` or `LIMIT` are grouped,
+/// so operators apply in the order they're called.
+/// ```
+/// use typed_sql::{Query, Table, ToSql};
+/// use typed_sql::dialect::Sqlite;
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Admin {
+///     id: i64
+/// }
+///
+/// let stmt = User::table()
+///     .select_with(|user| user.id)
+///     .union(Admin::table().select_with(|admin| admin.id))
+///     .intersect(User::table().select_with(|user| user.id).limit(1));
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "(SELECT users.id FROM users UNION SELECT admins.id FROM admins) \
+///     INTERSECT (SELECT users.id FROM users LIMIT 1);"
+/// );
+///
+/// assert_eq!(
+///     stmt.to_sql_for::<Sqlite>(),
+///     "SELECT * FROM (SELECT users.id FROM users UNION SELECT admins.id FROM admins) \
+///     INTERSECT SELECT * FROM (SELECT users.id FROM users LIMIT 1);"
+/// );
+/// ```
+pub struct Compound<L, R, O> {
+    lhs: L,
+    rhs: R,
+    _operator: PhantomData<O>,
+}
+
+impl<L, R, O> Compound<L, R, O> {
+    pub(crate) fn new(lhs: L, rhs: R) -> Self {
+        Self {
+            lhs,
+            rhs,
+            _operator: PhantomData,
+        }
+    }
+}
+
+impl<L, R, O> Compound<L, R, O>
+where
+    L: Select,
+    R: Select,
+    O: SetOperator,
+{
+    /// Orders the combined rows by the names of the columns from `f`.
+    /// ```
+    /// use typed_sql::{Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String
+    /// }
+    ///
+    /// #[derive(Table)]
+    /// struct Admin {
+    ///     id: i64,
+    ///     name: String
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .select_with(|user| (user.id, user.name))
+    ///     .union(Admin::table().select_with(|admin| (admin.id, admin.name)))
+    ///     .order_by(|user| user.name)
+    ///     .limit(10);
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT users.id,users.name FROM users \
+    ///     UNION SELECT admins.id,admins.name FROM admins \
+    ///     ORDER BY name LIMIT 10;"
+    /// );
+    /// ```
+    pub fn order_by<F, P>(self, f: F) -> CompoundOrderBy<Self, P>
+    where
+        F: FnOnce(<L::Selectable as Selectable>::Fields) -> P,
+        P: Order,
+    {
+        CompoundOrderBy {
+            stmt: self,
+            order: f(Default::default()),
+        }
+    }
+}
+
+impl<L, R, O> Select for Compound<L, R, O>
+where
+    L: Select,
+    R: Select,
+    O: SetOperator,
+{
+    type Selectable = L::Selectable;
+    type Queryable = L::Queryable;
+
+    const IS_COMPOUND: bool = true;
+}
+
+impl<L, R, O> ToSql for Compound<L, R, O>
+where
+    L: Select,
+    R: Select,
+    O: SetOperator,
+{
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
+        write_operand(&self.lhs, sql);
+        sql.push(' ');
+        sql.push_str(O::OPERATOR);
+        sql.push(' ');
+        write_operand(&self.rhs, sql);
+    }
+}
+
+fn write_operand<S: Select, D: Dialect>(select: &S, sql: &mut Writer<D>) {
+    if S::IS_COMPOUND {
+        D::write_compound_operand(sql, select);
+    } else {
+        select.write_sql_unchecked(sql);
+    }
+}
+
+impl<L: CheckedSql, R: CheckedSql, O> CheckedSql for Compound<L, R, O> {}
+
+pub struct CompoundOrderBy<C, P> {
+    stmt: C,
+    order: P,
+}
+
+impl<C, P> Select for CompoundOrderBy<C, P>
+where
+    C: Select,
+    P: Order,
+{
+    type Selectable = C::Selectable;
+    type Queryable = C::Queryable;
+
+    const IS_COMPOUND: bool = true;
+
+    const IS_ORDERED: bool = true;
+}
+
+impl<C, P> ToSql for CompoundOrderBy<C, P>
+where
+    C: Select,
+    P: Order,
+{
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.stmt.write_sql_unchecked(sql);
+        sql.push_str(" ORDER BY ");
+        self.order.write_output_order(sql);
+    }
+}
+
+impl<C: CheckedSql, P> CheckedSql for CompoundOrderBy<C, P> {}
+
+/// Selects `R` return the same rows as `L`.
+pub trait SameRows<R> {}
+
+impl<L, R> SameRows<R> for L
+where
+    L: Select,
+    R: Select,
+    L::Queryable: Rows<L::Selectable>,
+    R::Queryable: Rows<R::Selectable, Row = <L::Queryable as Rows<L::Selectable>>::Row>,
+{
+}
@@ -1,19 +1,21 @@
-use super::Select;
+use super::{Select, Selectable};
+use crate::dialect::Dialect;
+use crate::query::Predicate;
 use crate::{
     sql::CheckedSql,
     types::field::{Field, Then},
 };
-use crate::{Table, ToSql};
+use crate::{Table, ToSql, Writer};
 
 pub trait GroupOrder {
-    fn write_columns(&self, sql: &mut String);
+    fn write_columns<D: Dialect>(&self, sql: &mut Writer<D>);
 }
 
 impl<T, A> GroupOrder for Field<T, A>
 where
     T: Table,
 {
-    fn write_columns(&self, sql: &mut String) {
+    fn write_columns<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.write_field(sql);
     }
 }
@@ -23,7 +25,7 @@ where
     H: GroupOrder,
     T: GroupOrder,
 {
-    fn write_columns(&self, sql: &mut String) {
+    fn write_columns<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.head.write_columns(sql);
         sql.push(',');
         self.tail.write_columns(sql);
@@ -41,6 +43,47 @@ impl<Q, O> GroupBy<Q, O> {
     }
 }
 
+impl<S, O> GroupBy<S, O>
+where
+    S: Select,
+    O: GroupOrder,
+{
+    /// Filters the groups of this select.
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    /// use typed_sql::query::{count_all, sum};
+    ///
+    /// #[derive(Table)]
+    /// struct Order {
+    ///     customer_id: i64,
+    ///     amount: i64
+    /// }
+    ///
+    /// let stmt = Order::table()
+    ///     .select()
+    ///     .group_by(|order| order.customer_id)
+    ///     .having(|order| count_all().gt(5).and(sum(order.amount).lt(100)))
+    ///     .order_by(|order| order.customer_id);
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM orders GROUP BY orders.customer_id \
+    ///     HAVING COUNT(*) > 5 AND CAST(SUM(orders.amount) AS BIGINT) < 100 \
+    ///     ORDER BY orders.customer_id;"
+    /// );
+    /// ```
+    pub fn having<F, P>(self, f: F) -> Having<Self, P>
+    where
+        F: FnOnce(<S::Selectable as Selectable>::Fields) -> P,
+        P: Predicate,
+    {
+        Having {
+            stmt: self,
+            predicate: f(Default::default()),
+        }
+    }
+}
+
 impl<S, O> Select for GroupBy<S, O>
 where
     S: Select,
@@ -55,7 +98,7 @@ where
     S: Select,
     O: GroupOrder,
 {
-    fn write_sql_unchecked(&self, sql: &mut String) {
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.stmt.write_sql_unchecked(sql);
         sql.push_str(" GROUP BY ");
         self.order.write_columns(sql);
@@ -63,3 +106,31 @@ where
 }
 
 impl<Q: CheckedSql, O> CheckedSql for GroupBy<Q, O> {}
+
+pub struct Having<Q, P> {
+    stmt: Q,
+    predicate: P,
+}
+
+impl<S, P> Select for Having<S, P>
+where
+    S: Select,
+    P: Predicate,
+{
+    type Selectable = S::Selectable;
+    type Queryable = S::Queryable;
+}
+
+impl<S, P> ToSql for Having<S, P>
+where
+    S: Select,
+    P: Predicate,
+{
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.stmt.write_sql_unchecked(sql);
+        sql.push_str(" HAVING ");
+        self.predicate.write_predicate(sql);
+    }
+}
+
+impl<Q: CheckedSql, P: CheckedSql> CheckedSql for Having<Q, P> {}
@@ -1,21 +1,58 @@
 use super::SelectStatement;
+use crate::dialect::Dialect;
 use crate::query::select::WildCard;
 use crate::query::Predicate;
-use crate::Table;
+use crate::{Table, Writer};
 use std::marker::PhantomData;
 
 pub struct Inner;
 
+pub struct Left;
+
+pub struct Right;
+
+pub struct Full;
+
+/// A join without a predicate, pairing every row of both tables.
+pub struct Cross;
+
 pub trait JoinKind {
     const KIND: &'static str;
+
+    /// Whether this is a `FULL JOIN`, which some dialects don't support.
+    const IS_FULL: bool = false;
 }
 
 impl JoinKind for Inner {
     const KIND: &'static str = "INNER";
 }
 
+impl JoinKind for Left {
+    const KIND: &'static str = "LEFT";
+}
+
+impl JoinKind for Right {
+    const KIND: &'static str = "RIGHT";
+}
+
+impl JoinKind for Full {
+    const KIND: &'static str = "FULL";
+
+    const IS_FULL: bool = true;
+}
+
+/// Fails to compile a `FULL JOIN` for a dialect without it, such as MySQL.
+struct AssertJoin<D, K>(PhantomData<(D, K)>);
+
+impl<D: Dialect, K: JoinKind> AssertJoin<D, K> {
+    const OK: () = assert!(
+        !K::IS_FULL || D::FULL_JOIN,
+        "this dialect can't write a `FULL JOIN`"
+    );
+}
+
 /// ```
-/// use typed_sql::{Join, Query, Table, ToSql};
+/// use typed_sql::{Comparable, Join, Query, Table, ToSql};
 /// use typed_sql::query::Joined;
 ///
 /// #[derive(Table)]
@@ -44,6 +81,239 @@ impl JoinKind for Inner {
 ///     "SELECT * FROM users INNER JOIN posts ON users.id = posts.user_id;"
 /// );
 /// ```
+///
+/// Every field after the first is joined in declaration order.
+/// ```
+/// use typed_sql::{Comparable, Join, Query, Table, ToSql};
+/// use typed_sql::query::Joined;
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Post {
+///     id: i64,
+///     user_id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Comment {
+///     post_id: i64,
+///     user_id: i64
+/// }
+///
+/// #[derive(Join)]
+/// struct Thread {
+///    author: User,
+///    parent: Post,
+///    reply: Comment
+/// }
+///
+/// let join = Thread::join(|join| ThreadJoin {
+///     parent: Joined::new(join.author.id.eq(join.parent.user_id)),
+///     reply: Joined::new(join.parent.id.eq(join.reply.post_id)),
+/// });
+///
+/// assert_eq!(
+///     join.select().to_sql(),
+///     "SELECT * FROM users \
+///     INNER JOIN posts ON users.id = posts.user_id \
+///     INNER JOIN comments ON posts.id = comments.post_id;"
+/// );
+/// ```
+///
+/// A join needs the base table and at least one joined table.
+/// ```compile_fail
+/// use typed_sql::{Join, Table};
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64
+/// }
+///
+/// #[derive(Join)]
+/// struct Lonely {
+///    user: User
+/// }
+/// ```
+///
+/// Outer joins are chosen with `#[join(left)]`, `#[join(right)]` or `#[join(full)]`.
+/// The columns of a table that may be missing from a row are nullable,
+/// so they're compared with optional values.
+/// ```
+/// use typed_sql::{Comparable, Join, Query, Table, ToSql};
+/// use typed_sql::query::Joined;
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Post {
+///     id: i64,
+///     user_id: i64
+/// }
+///
+/// #[derive(Join)]
+/// struct UserPost {
+///    user: User,
+///    #[join(left)]
+///    post: Post
+/// }
+///
+/// let join = UserPost::join(|join| UserPostJoin {
+///     post: Joined::new(join.user.id.eq(join.post.user_id)),
+/// });
+///
+/// let stmt = join.select().filter(|join| join.post.id.eq(None));
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "SELECT * FROM users LEFT JOIN posts ON users.id = posts.user_id WHERE posts.id IS NULL;"
+/// );
+/// ```
+///
+/// SQLite supports right and full joins since version 3.39,
+/// and MySQL has no full joins, so they fail to compile for it.
+/// ```compile_fail
+/// use typed_sql::{Comparable, Join, Query, Table, ToSql};
+/// use typed_sql::dialect::MySql;
+/// use typed_sql::query::Joined;
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Post {
+///     user_id: i64
+/// }
+///
+/// #[derive(Join)]
+/// struct UserPost {
+///    user: User,
+///    #[join(full)]
+///    post: Post
+/// }
+///
+/// UserPost::join(|join| UserPostJoin {
+///     post: Joined::new(join.user.id.eq(join.post.user_id)),
+/// })
+/// .select()
+/// .to_sql_for::<MySql>();
+/// ```
+///
+/// Cross joins with `#[join(cross)]` have no predicate.
+/// ```
+/// use typed_sql::{Join, Query, Table, ToSql};
+/// use typed_sql::query::Joined;
+///
+/// #[derive(Table)]
+/// struct Size {
+///     id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Color {
+///     id: i64
+/// }
+///
+/// #[derive(Join)]
+/// struct Variant {
+///    size: Size,
+///    #[join(cross)]
+///    color: Color
+/// }
+///
+/// let join = Variant::join(|_| VariantJoin {
+///     color: Joined::cross(),
+/// });
+///
+/// assert_eq!(join.select().to_sql(), "SELECT * FROM sizes CROSS JOIN colors;");
+/// ```
+///
+/// Fields can have any name, even one like the generics of the derived impls.
+/// ```
+/// use typed_sql::{Comparable, Join, Query, Table, ToSql};
+/// use typed_sql::query::Joined;
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Post {
+///     user_id: i64
+/// }
+///
+/// #[derive(Join)]
+/// struct UserPost {
+///    t: User,
+///    d: Post
+/// }
+///
+/// let join = UserPost::join(|join| UserPostJoin {
+///     d: Joined::new(join.t.id.eq(join.d.user_id)),
+/// });
+///
+/// assert_eq!(
+///     join.select().to_sql(),
+///     "SELECT * FROM users INNER JOIN posts ON users.id = posts.user_id;"
+/// );
+/// ```
+///
+/// That includes raw identifiers and names that only differ in their underscores.
+/// ```
+/// use typed_sql::{Comparable, Join, Query, Table, ToSql};
+/// use typed_sql::query::Joined;
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Post {
+///     user_id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Comment {
+///     user_id: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Like {
+///     user_id: i64
+/// }
+///
+/// #[derive(Join)]
+/// struct Activity {
+///    user: User,
+///    r#type: Post,
+///    user_post: Comment,
+///    user__post: Like
+/// }
+///
+/// let join = Activity::join(|join| ActivityJoin {
+///     r#type: Joined::new(join.user.id.eq(join.r#type.user_id)),
+///     user_post: Joined::new(join.user.id.eq(join.user_post.user_id)),
+///     user__post: Joined::new(join.user.id.eq(join.user__post.user_id)),
+/// });
+///
+/// assert_eq!(
+///     join.select().to_sql(),
+///     "SELECT * FROM users \
+///     INNER JOIN posts ON users.id = posts.user_id \
+///     INNER JOIN comments ON users.id = comments.user_id \
+///     INNER JOIN likes ON users.id = likes.user_id;"
+/// );
+/// ```
 pub trait Join<P> {
     type Table: Table;
     type Fields: Default;
@@ -61,7 +331,7 @@ pub trait JoinSelect {
     type Table: Table;
     type Fields: Default;
 
-    fn write_join_select(&self, sql: &mut String);
+    fn write_join_select<D: Dialect>(&self, sql: &mut Writer<D>);
 
     fn select(self) -> SelectStatement<Self, WildCard>
     where
@@ -91,12 +361,30 @@ where
         }
     }
 
-    pub fn write_join(&self, sql: &mut String) {
+    /// This fails to compile for a `FULL JOIN` in dialects without it.
+    pub fn write_join<D: Dialect>(&self, sql: &mut Writer<D>) {
+        let () = AssertJoin::<D, K>::OK;
+
         sql.push(' ');
         sql.push_str(K::KIND);
         sql.push_str(" JOIN ");
-        sql.push_str(T::NAME);
+        T::write_table(sql);
         sql.push_str(" ON ");
         self.predicate.write_predicate(sql);
     }
 }
+
+impl<T: Table> Joined<(), Cross, T> {
+    pub fn cross() -> Self {
+        Self {
+            predicate: (),
+            _kind: PhantomData,
+            _table: PhantomData,
+        }
+    }
+
+    pub fn write_join<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str(" CROSS JOIN ");
+        T::write_table(sql);
+    }
+}
@@ -1,27 +1,45 @@
-use std::fmt::Write;
-
 use super::filter::Filter;
 use super::Predicate;
-use crate::sql::{CheckedSql, ToSql};
+use crate::dialect::Dialect;
+use crate::sql::{CheckedSql, ToSql, Writer};
+
+pub mod aggregate;
+pub use aggregate::Aggregate;
+
+pub mod compound;
+pub use compound::Compound;
 
 pub mod group;
-pub use group::{GroupBy, GroupOrder};
+pub use group::{GroupBy, GroupOrder, Having};
 
 pub mod join;
 pub use join::{Join, Joined};
 
 pub mod order;
-pub use order::{Order, OrderBy};
+pub use order::{After, Keyset, Order, OrderBy};
 
 pub mod queryable;
-pub use queryable::{Queryable, WildCard, WriteQueryable};
+pub use queryable::{Projection, Queryable, Scalar, WildCard, WriteQueryable};
 
 mod selectable;
 pub use selectable::{SelectStatement, Selectable};
 
+pub mod subquery;
+pub use subquery::Subquery;
+
+pub mod window;
+pub use window::{Over, Window, WindowFunction};
+
 pub trait Select: ToSql {
     type Selectable: Selectable;
     type Queryable: WriteQueryable;
+
+    /// Whether this select ends with an `ORDER BY`, `LIMIT` or set operator,
+    /// which is grouped when it's the operand of a compound select.
+    const IS_COMPOUND: bool = false;
+
+    /// Whether this select ends with an `ORDER BY`.
+    const IS_ORDERED: bool = false;
 }
 
 impl<S, Q> Select for SelectStatement<S, Q>
@@ -43,29 +61,46 @@ where
     type Queryable = Q;
 }
 
+/// A `LIMIT` and `OFFSET` on a select, written in the dialect's syntax.
 #[derive(Debug, Clone, Copy)]
 pub struct Limit<Q> {
     queryable: Q,
-    limit: usize,
+    limit: Option<usize>,
+    offset: Option<usize>,
 }
 
 impl<Q> Limit<Q> {
     #[inline(always)]
-    pub(crate) const fn new(queryable: Q, limit: usize) -> Self {
-        Self { queryable, limit }
+    pub(crate) const fn new(queryable: Q, limit: Option<usize>, offset: Option<usize>) -> Self {
+        Self {
+            queryable,
+            limit,
+            offset,
+        }
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
     }
 }
 
 impl<Q: Select> Select for Limit<Q> {
     type Selectable = Q::Selectable;
     type Queryable = Q::Queryable;
+
+    const IS_COMPOUND: bool = true;
 }
 
 impl<Q: Select> ToSql for Limit<Q> {
-    fn write_sql_unchecked(&self, sql: &mut String) {
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.queryable.write_sql_unchecked(sql);
-        sql.write_fmt(format_args!(" LIMIT {}", self.limit))
-            .unwrap();
+        D::write_limit(sql, self.limit, self.offset, Q::IS_ORDERED);
     }
 }
 
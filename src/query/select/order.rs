@@ -1,9 +1,12 @@
 use super::Select;
+use crate::dialect::Dialect;
+use crate::query::Predicate;
+use crate::types::Expr;
 use crate::{
     sql::CheckedSql,
     types::field::{Field, Then},
 };
-use crate::{Table, ToSql};
+use crate::{Table, ToSql, Writer};
 use std::marker::PhantomData;
 
 pub struct OrderBy<Q, O> {
@@ -24,6 +27,10 @@ where
 {
     type Selectable = S::Selectable;
     type Queryable = S::Queryable;
+
+    const IS_COMPOUND: bool = true;
+
+    const IS_ORDERED: bool = true;
 }
 
 impl<Q, O> ToSql for OrderBy<Q, O>
@@ -31,7 +38,7 @@ where
     Q: Select,
     O: Order,
 {
-    fn write_sql_unchecked(&self, sql: &mut String) {
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.stmt.write_sql_unchecked(sql);
         sql.push_str(" ORDER BY ");
         self.order.write_order(sql);
@@ -42,18 +49,24 @@ impl<Q: CheckedSql, O> CheckedSql for OrderBy<Q, O> {}
 
 pub trait Direction {
     const DIRECTION: &'static str;
+
+    const ASCENDING: bool;
 }
 
 pub struct Ascending;
 
 impl Direction for Ascending {
     const DIRECTION: &'static str = "ASC";
+
+    const ASCENDING: bool = true;
 }
 
 pub struct Descending;
 
 impl Direction for Descending {
     const DIRECTION: &'static str = "DESC";
+
+    const ASCENDING: bool = false;
 }
 
 pub struct Ordered<T, A, D> {
@@ -78,27 +91,204 @@ impl<T, A, D> Ordered<T, A, D> {
 }
 
 pub trait Order {
-    fn write_order(&self, sql: &mut String);
+    fn write_order<D: Dialect>(&self, sql: &mut Writer<D>);
+
+    /// Writes this order by the names of the selected columns, for compound selects.
+    fn write_output_order<D: Dialect>(&self, sql: &mut Writer<D>);
 }
 
-impl<T: Table, A, D: Direction> Order for Ordered<T, A, D> {
-    fn write_order(&self, sql: &mut String) {
+impl<T: Table, A, Dir: Direction> Order for Ordered<T, A, Dir> {
+    fn write_order<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.field.write_field(sql);
         sql.push(' ');
-        sql.push_str(D::DIRECTION);
+        sql.push_str(Dir::DIRECTION);
+    }
+
+    fn write_output_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.field.write_column(sql);
+        sql.push(' ');
+        sql.push_str(Dir::DIRECTION);
     }
 }
 
 impl<H: Order, T: Order> Order for Then<H, T> {
-    fn write_order(&self, sql: &mut String) {
+    fn write_order<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.head.write_order(sql);
         sql.push(',');
         self.tail.write_order(sql);
     }
+
+    fn write_output_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.head.write_output_order(sql);
+        sql.push(',');
+        self.tail.write_output_order(sql);
+    }
 }
 
 impl<T: Table, A> Order for Field<T, A> {
-    fn write_order(&self, sql: &mut String) {
+    fn write_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_field(sql);
+    }
+
+    fn write_output_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_column(sql);
+    }
+}
+
+/// An ordering that can be continued after the values `V` of the last row of a page.
+///
+/// The values of a column are a tuple of the values of each column,
+/// nested the same way as the columns are joined with `then`.
+pub trait Keyset<V>: Order {
+    /// `Some(true)` if every column is ascending, `Some(false)` if every column
+    /// is descending and `None` if the directions are mixed.
+    const ASCENDING: Option<bool>;
+
+    const LEN: usize;
+
+    fn write_keys<D: Dialect>(&self, sql: &mut Writer<D>);
+
+    fn write_values<D: Dialect>(values: &V, sql: &mut Writer<D>);
+
+    /// Writes the expanded predicate for rows after `values`.
+    fn write_after<D: Dialect>(&self, values: &V, sql: &mut Writer<D>);
+
+    fn write_equal<D: Dialect>(&self, values: &V, sql: &mut Writer<D>);
+}
+
+fn write_key_comparison<T, A, V, D>(field: &Field<T, A>, op: &str, value: &V, sql: &mut Writer<D>)
+where
+    T: Table,
+    V: Expr<A>,
+    D: Dialect,
+{
+    field.write_field(sql);
+    sql.push_str(op);
+    value.write_expr(sql);
+}
+
+impl<T: Table, A, V: Expr<A>> Keyset<V> for Field<T, A> {
+    const ASCENDING: Option<bool> = Some(true);
+
+    const LEN: usize = 1;
+
+    fn write_keys<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.write_field(sql);
     }
+
+    fn write_values<D: Dialect>(values: &V, sql: &mut Writer<D>) {
+        values.write_expr(sql);
+    }
+
+    fn write_after<D: Dialect>(&self, values: &V, sql: &mut Writer<D>) {
+        write_key_comparison(self, " > ", values, sql);
+    }
+
+    fn write_equal<D: Dialect>(&self, values: &V, sql: &mut Writer<D>) {
+        write_key_comparison(self, " = ", values, sql);
+    }
+}
+
+impl<T: Table, A, Dir: Direction, V: Expr<A>> Keyset<V> for Ordered<T, A, Dir> {
+    const ASCENDING: Option<bool> = Some(Dir::ASCENDING);
+
+    const LEN: usize = 1;
+
+    fn write_keys<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.field.write_field(sql);
+    }
+
+    fn write_values<D: Dialect>(values: &V, sql: &mut Writer<D>) {
+        values.write_expr(sql);
+    }
+
+    fn write_after<D: Dialect>(&self, values: &V, sql: &mut Writer<D>) {
+        let op = if Dir::ASCENDING { " > " } else { " < " };
+        write_key_comparison(&self.field, op, values, sql);
+    }
+
+    fn write_equal<D: Dialect>(&self, values: &V, sql: &mut Writer<D>) {
+        write_key_comparison(&self.field, " = ", values, sql);
+    }
+}
+
+impl<H, T, VH, VT> Keyset<(VH, VT)> for Then<H, T>
+where
+    H: Keyset<VH>,
+    T: Keyset<VT>,
+{
+    const ASCENDING: Option<bool> = match (H::ASCENDING, T::ASCENDING) {
+        (Some(head), Some(tail)) if head == tail => Some(head),
+        _ => None,
+    };
+
+    const LEN: usize = H::LEN + T::LEN;
+
+    fn write_keys<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.head.write_keys(sql);
+        sql.push(',');
+        self.tail.write_keys(sql);
+    }
+
+    fn write_values<D: Dialect>(values: &(VH, VT), sql: &mut Writer<D>) {
+        H::write_values(&values.0, sql);
+        sql.push(',');
+        T::write_values(&values.1, sql);
+    }
+
+    /// Rows are after `values` if their head is after it,
+    /// or if their head is equal and their tail is after it.
+    fn write_after<D: Dialect>(&self, values: &(VH, VT), sql: &mut Writer<D>) {
+        self.head.write_after(&values.0, sql);
+        sql.push_str(" OR ");
+        self.head.write_equal(&values.0, sql);
+        sql.push_str(" AND ");
+        if T::LEN > 1 {
+            sql.push('(');
+            self.tail.write_after(&values.1, sql);
+            sql.push(')');
+        } else {
+            self.tail.write_after(&values.1, sql);
+        }
+    }
+
+    fn write_equal<D: Dialect>(&self, values: &(VH, VT), sql: &mut Writer<D>) {
+        self.head.write_equal(&values.0, sql);
+        sql.push_str(" AND ");
+        self.tail.write_equal(&values.1, sql);
+    }
 }
+
+/// Rows ordered after the last row of a page.
+pub struct After<O, V> {
+    order: O,
+    values: V,
+}
+
+impl<O, V> After<O, V> {
+    pub(crate) fn new(order: O, values: V) -> Self {
+        Self { order, values }
+    }
+}
+
+impl<O: Keyset<V>, V> Predicate for After<O, V> {
+    fn write_predicate<D: Dialect>(&self, sql: &mut Writer<D>) {
+        match O::ASCENDING {
+            Some(_) if O::LEN == 1 => self.order.write_after(&self.values, sql),
+            Some(ascending) if D::ROW_VALUES => {
+                sql.push('(');
+                self.order.write_keys(sql);
+                sql.push_str(if ascending { ") > (" } else { ") < (" });
+                O::write_values(&self.values, sql);
+                sql.push(')');
+            }
+            _ => {
+                sql.push('(');
+                self.order.write_after(&self.values, sql);
+                sql.push(')');
+            }
+        }
+    }
+}
+
+impl<O, V: CheckedSql> CheckedSql for After<O, V> {}
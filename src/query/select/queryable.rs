@@ -1,5 +1,7 @@
+use super::{GroupOrder, Selectable};
+use crate::dialect::Dialect;
 use crate::types::Field;
-use crate::Table;
+use crate::{Table, Writer};
 use std::marker::PhantomData;
 
 pub struct QueryableWriter<Q: ?Sized> {
@@ -7,13 +9,87 @@ pub struct QueryableWriter<Q: ?Sized> {
 }
 
 impl<Q: Queryable + ?Sized> WriteQueryable for QueryableWriter<Q> {
-    fn write_query(&self, sql: &mut String) {
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
         Q::write_queryable(sql);
     }
 }
 
+/// Columns selected into a struct, which are written qualified with `#[queryable(table = ...)]`.
+///
+/// ```
+/// use typed_sql::{Comparable, Join, Query, Queryable, Table, ToSql};
+/// use typed_sql::query::Joined;
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64,
+///     name: String
+/// }
+///
+/// #[derive(Table)]
+/// struct Post {
+///     id: i64,
+///     user_id: i64
+/// }
+///
+/// #[derive(Join)]
+/// struct UserPost {
+///     user: User,
+///     post: Post
+/// }
+///
+/// #[derive(Queryable)]
+/// #[queryable(table = User)]
+/// struct Author {
+///     #[column(rename = "id")]
+///     user_id: i64,
+///     name: Option<String>
+/// }
+///
+/// let stmt = UserPost::join(|join| UserPostJoin {
+///     post: Joined::new(join.user.id.eq(join.post.user_id)),
+/// })
+/// .query(Author::queryable());
+///
+/// assert_eq!(
+///     stmt.to_sql_unchecked(),
+///     "SELECT users.id AS user_id,users.name \
+///     FROM users INNER JOIN posts ON users.id = posts.user_id;"
+/// );
+/// ```
+///
+/// Each field must be a column of the table, with the same type or an `Option` of it.
+/// ```compile_fail
+/// use typed_sql::{Queryable, Table};
+///
+/// #[derive(Table)]
+/// struct User {
+///     name: String
+/// }
+///
+/// #[derive(Queryable)]
+/// #[queryable(table = User)]
+/// struct Author {
+///     name: i64
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use typed_sql::{Queryable, Table};
+///
+/// #[derive(Table)]
+/// struct User {
+///     name: String
+/// }
+///
+/// #[derive(Queryable)]
+/// #[queryable(table = User)]
+/// struct Author {
+///     nmae: String
+/// }
+/// ```
 pub trait Queryable {
-    fn write_queryable(sql: &mut String);
+    fn write_queryable<D: Dialect>(sql: &mut Writer<D>);
 
     fn queryable() -> QueryableWriter<Self> {
         QueryableWriter {
@@ -23,13 +99,51 @@ pub trait Queryable {
 }
 
 pub trait WriteQueryable {
-    fn write_query(&self, sql: &mut String);
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>);
 }
 
+/// The rows returned by this query from `S`, which must be the same
+/// for selects to be combined.
+pub trait Rows<S> {
+    type Row: ?Sized;
+}
+
+impl<S: Selectable> Rows<S> for WildCard {
+    type Row = S::Table;
+}
+
+impl<S: Selectable> Rows<S> for Distinct<WildCard> {
+    type Row = S::Table;
+}
+
+impl<S, Q: Queryable + ?Sized> Rows<S> for QueryableWriter<Q> {
+    type Row = Q;
+}
+
+impl<S, P: Projection> Rows<S> for P {
+    type Row = P::Output;
+}
+
+/// A column of type `Self` can be read into a value of type `B`,
+/// which is the same type or an `Option` of it.
+pub trait ReadAs<B> {}
+
+impl<A> ReadAs<A> for A {}
+
+impl<A> ReadAs<Option<A>> for A {}
+
+/// The columns of a select, which are fetched as a value of type `Output`.
+pub trait Projection: WriteQueryable {
+    type Output;
+}
+
+/// A projection of a single column, which can be used as a value.
+pub trait Scalar: Projection {}
+
 pub struct WildCard;
 
 impl WriteQueryable for WildCard {
-    fn write_query(&self, sql: &mut String) {
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
         sql.push('*');
     }
 }
@@ -45,25 +159,171 @@ impl<T> Count<T> {
 }
 
 impl WriteQueryable for Count<()> {
-    fn write_query(&self, sql: &mut String) {
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
         write_wildcard(sql);
     }
 }
 
 impl WriteQueryable for Count<WildCard> {
-    fn write_query(&self, sql: &mut String) {
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
         write_wildcard(sql);
     }
 }
 
 impl<T: Table, A> WriteQueryable for Count<Field<T, A>> {
-    fn write_query(&self, sql: &mut String) {
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
         sql.push_str("COUNT(");
         self.column.write_field(sql);
         sql.push(')');
     }
 }
 
-fn write_wildcard(sql: &mut String) {
+impl Projection for Count<()> {
+    type Output = i64;
+}
+
+impl Scalar for Count<()> {}
+
+impl Projection for Count<WildCard> {
+    type Output = i64;
+}
+
+impl Scalar for Count<WildCard> {}
+
+impl<T: Table, A> Projection for Count<Field<T, A>> {
+    type Output = i64;
+}
+
+impl<T: Table, A> Scalar for Count<Field<T, A>> {}
+
+impl<T: Table, A> WriteQueryable for Count<Distinct<Field<T, A>>> {
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("COUNT(DISTINCT ");
+        self.column.inner.write_field(sql);
+        sql.push(')');
+    }
+}
+
+impl<T: Table, A> Projection for Count<Distinct<Field<T, A>>> {
+    type Output = i64;
+}
+
+impl<T: Table, A> Scalar for Count<Distinct<Field<T, A>>> {}
+
+/// Removes duplicate rows from a select, or duplicate values from an aggregate.
+pub struct Distinct<Q> {
+    inner: Q,
+}
+
+impl<Q> Distinct<Q> {
+    pub(crate) fn new(inner: Q) -> Self {
+        Self { inner }
+    }
+}
+
+impl<Q: WriteQueryable> WriteQueryable for Distinct<Q> {
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("DISTINCT ");
+        self.inner.write_query(sql);
+    }
+}
+
+impl<Q: Projection> Projection for Distinct<Q> {
+    type Output = Q::Output;
+}
+
+impl<Q: Scalar> Scalar for Distinct<Q> {}
+
+/// Keeps the first row of each group of rows with equal `columns` (PostgreSQL only).
+pub struct DistinctOn<O, Q> {
+    columns: O,
+    inner: Q,
+}
+
+impl<O, Q> DistinctOn<O, Q> {
+    pub(crate) fn new(columns: O, inner: Q) -> Self {
+        Self { columns, inner }
+    }
+}
+
+/// Fails to compile a `DISTINCT ON` for a dialect without it, such as MySQL or SQLite.
+struct AssertDistinctOn<D>(PhantomData<D>);
+
+impl<D: Dialect> AssertDistinctOn<D> {
+    const OK: () = assert!(
+        D::DISTINCT_ON,
+        "this dialect can't select `DISTINCT ON` columns"
+    );
+}
+
+impl<O: GroupOrder, Q: WriteQueryable> WriteQueryable for DistinctOn<O, Q> {
+    /// This fails to compile for dialects without `DISTINCT ON`.
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
+        let () = AssertDistinctOn::<D>::OK;
+
+        sql.push_str("DISTINCT ON (");
+        self.columns.write_columns(sql);
+        sql.push_str(") ");
+        self.inner.write_query(sql);
+    }
+}
+
+impl<O: GroupOrder, Q: Projection> Projection for DistinctOn<O, Q> {
+    type Output = Q::Output;
+}
+
+impl<O: GroupOrder, Q: Scalar> Scalar for DistinctOn<O, Q> {}
+
+/// A column is selected as its own type.
+impl<T: Table, A> WriteQueryable for Field<T, A> {
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_field(sql);
+    }
+}
+
+impl<T: Table, A> Projection for Field<T, A> {
+    type Output = A;
+}
+
+impl<T: Table, A> Scalar for Field<T, A> {}
+
+/// Tuples of projections are selected as tuples of their outputs.
+macro_rules! impl_projection_tuple {
+    ($($ty:ident),*) => {
+        impl<$($ty: WriteQueryable),*> WriteQueryable for ($($ty,)*) {
+            #[allow(non_snake_case)]
+            fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
+                let ($($ty,)*) = self;
+                let mut _is_first = true;
+                $(
+                    if !_is_first {
+                        sql.push(',');
+                    }
+                    _is_first = false;
+                    $ty.write_query(sql);
+                )*
+            }
+        }
+
+        impl<$($ty: Projection),*> Projection for ($($ty,)*) {
+            type Output = ($($ty::Output,)*);
+        }
+    };
+}
+
+impl_projection_tuple!(P1);
+impl_projection_tuple!(P1, P2);
+impl_projection_tuple!(P1, P2, P3);
+impl_projection_tuple!(P1, P2, P3, P4);
+impl_projection_tuple!(P1, P2, P3, P4, P5);
+impl_projection_tuple!(P1, P2, P3, P4, P5, P6);
+impl_projection_tuple!(P1, P2, P3, P4, P5, P6, P7);
+impl_projection_tuple!(P1, P2, P3, P4, P5, P6, P7, P8);
+impl_projection_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9);
+impl_projection_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10);
+impl_projection_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11);
+impl_projection_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12);
+
+fn write_wildcard<D: Dialect>(sql: &mut Writer<D>) {
     sql.push_str("COUNT(*)");
 }
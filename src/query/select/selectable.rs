@@ -1,27 +1,29 @@
 use super::join::JoinSelect;
-use super::WriteQueryable;
+use super::queryable::{Distinct, DistinctOn};
+use super::{GroupOrder, WriteQueryable};
+use crate::dialect::Dialect;
 use crate::table::{Table, TableQuery};
-use crate::{CheckedSql, ToSql};
+use crate::{CheckedSql, ToSql, Writer};
 
 pub trait Selectable {
     type Table: Table + ?Sized;
     type Fields: Default;
 
-    fn write_join(&self, sql: &mut String);
+    fn write_join<D: Dialect>(&self, sql: &mut Writer<D>);
 }
 
 impl<T: Table + ?Sized> Selectable for TableQuery<T> {
     type Table = T;
     type Fields = T::Fields;
 
-    fn write_join(&self, _sql: &mut String) {}
+    fn write_join<D: Dialect>(&self, _sql: &mut Writer<D>) {}
 }
 
 impl<J: JoinSelect> Selectable for J {
     type Table = J::Table;
     type Fields = J::Fields;
 
-    fn write_join(&self, sql: &mut String) {
+    fn write_join<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.write_join_select(sql);
     }
 }
@@ -36,6 +38,73 @@ impl<S: Selectable, Q> SelectStatement<S, Q> {
     pub fn new(from: S, query: Q) -> Self {
         Self { from, query }
     }
+
+    /// ```
+    /// use typed_sql::{Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     author: String
+    /// }
+    ///
+    /// let stmt = Post::table().select().distinct();
+    ///
+    /// assert_eq!(stmt.to_sql(), "SELECT DISTINCT * FROM posts;");
+    ///
+    /// let stmt = Post::table().count(|post| post.author.distinct());
+    ///
+    /// assert_eq!(stmt.to_sql(), "SELECT COUNT(DISTINCT posts.author) FROM posts;");
+    /// ```
+    pub fn distinct(self) -> SelectStatement<S, Distinct<Q>> {
+        SelectStatement::new(self.from, Distinct::new(self.query))
+    }
+
+    /// Selects the first row for each distinct value of the columns from `f`,
+    /// which is chosen by the `ORDER BY` (PostgreSQL only).
+    /// ```
+    /// use typed_sql::{Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     id: i64,
+    ///     author: String
+    /// }
+    ///
+    /// let stmt = Post::table()
+    ///     .select()
+    ///     .distinct_on(|post| post.author)
+    ///     .order_by(|post| post.author.then(post.id.descending()));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT DISTINCT ON (posts.author) * FROM posts ORDER BY posts.author,posts.id DESC;"
+    /// );
+    /// ```
+    ///
+    /// This fails to compile for other dialects.
+    /// ```compile_fail
+    /// use typed_sql::{Query, Table, ToSql};
+    /// use typed_sql::dialect::Sqlite;
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     id: i64,
+    ///     author: String
+    /// }
+    ///
+    /// Post::table()
+    ///     .select()
+    ///     .distinct_on(|post| post.author)
+    ///     .to_sql_for::<Sqlite>();
+    /// ```
+    pub fn distinct_on<F, O>(self, f: F) -> SelectStatement<S, DistinctOn<O, Q>>
+    where
+        F: FnOnce(S::Fields) -> O,
+        O: GroupOrder,
+    {
+        let columns = f(Default::default());
+        SelectStatement::new(self.from, DistinctOn::new(columns, self.query))
+    }
 }
 
 impl<S, Q> ToSql for SelectStatement<S, Q>
@@ -43,11 +112,11 @@ where
     S: Selectable,
     Q: WriteQueryable,
 {
-    fn write_sql_unchecked(&self, sql: &mut String) {
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
         sql.push_str("SELECT ");
         self.query.write_query(sql);
         sql.push_str(" FROM ");
-        sql.push_str(S::Table::NAME);
+        S::Table::write_table(sql);
         self.from.write_join(sql);
     }
 }
@@ -0,0 +1,83 @@
+use super::{Projection, Scalar, Select};
+use crate::dialect::Dialect;
+use crate::types::{Expr, TypedExpr};
+use crate::{CheckedSql, Writer};
+use std::marker::PhantomData;
+
+/// A select of a single value, used as a value of type `A`.
+///
+/// ```
+/// use typed_sql::{Comparable, Query, Table, ToSql};
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64,
+///     post_count: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Post {
+///     user_id: i64
+/// }
+///
+/// let stmt = User::table().select().filter(|user| {
+///     user.post_count.lt(Post::table()
+///         .count(|_| {})
+///         .filter(|post| post.user_id.eq(user.id))
+///         .subquery())
+/// });
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "SELECT * FROM users WHERE users.post_count < \
+///     (SELECT COUNT(*) FROM posts WHERE posts.user_id = users.id);"
+/// );
+/// ```
+pub struct Subquery<S, A> {
+    select: S,
+    _output: PhantomData<A>,
+}
+
+impl<S, A> Subquery<S, A> {
+    pub(crate) fn new(select: S) -> Self {
+        Self {
+            select,
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<S, A> Expr<A> for Subquery<S, A>
+where
+    S: Select,
+    S::Queryable: Scalar + Projection<Output = A>,
+{
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push('(');
+        self.select.write_sql_unchecked(sql);
+        sql.push(')');
+    }
+}
+
+/// A subquery that selects no rows is `NULL`.
+impl<S, A> TypedExpr for Subquery<S, A>
+where
+    S: Select,
+    S::Queryable: Scalar + Projection<Output = A>,
+{
+    type Type = A;
+}
+
+impl<S, A> Expr<Option<A>> for Subquery<S, A>
+where
+    S: Select,
+    S::Queryable: Scalar + Projection<Output = A>,
+{
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push('(');
+        self.select.write_sql_unchecked(sql);
+        sql.push(')');
+    }
+}
+
+impl<S: CheckedSql, A> CheckedSql for Subquery<S, A> {}
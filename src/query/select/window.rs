@@ -0,0 +1,419 @@
+use super::aggregate::{Aggregate, AggregateFunction};
+use super::{GroupOrder, Order, Projection, Scalar, WriteQueryable};
+use crate::dialect::Dialect;
+use crate::types::{Expr, Field, Nullable};
+use crate::{CheckedSql, Table, Writer};
+use std::fmt::Write;
+
+/// A function computed over a window of rows, which is selected as `Output`.
+pub trait WindowFunction {
+    type Output;
+
+    fn write_function<D: Dialect>(&self, sql: &mut Writer<D>);
+
+    /// Writes this function followed by the window from `write_window`.
+    fn write_over<D, W>(&self, sql: &mut Writer<D>, write_window: W)
+    where
+        D: Dialect,
+        W: FnOnce(&mut Writer<D>),
+    {
+        self.write_function(sql);
+        write_window(sql);
+    }
+}
+
+/// Every aggregate can also be computed over a window, such as a running `SUM`.
+impl<F, E, A> WindowFunction for Aggregate<F, E, A>
+where
+    F: AggregateFunction<A>,
+    E: Expr<A>,
+{
+    type Output = F::Output;
+
+    fn write_function<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_cast(sql, |_| {});
+    }
+
+    /// The window is written inside the cast of the aggregate.
+    fn write_over<D, W>(&self, sql: &mut Writer<D>, write_window: W)
+    where
+        D: Dialect,
+        W: FnOnce(&mut Writer<D>),
+    {
+        self.write_cast(sql, write_window);
+    }
+}
+
+impl<F, E, A> Aggregate<F, E, A>
+where
+    F: AggregateFunction<A>,
+    E: Expr<A>,
+{
+    /// Computes this aggregate over `window` for each row, instead of once per group.
+    /// ```
+    /// use typed_sql::{Query, Table, ToSql};
+    /// use typed_sql::query::{sum, window};
+    /// use typed_sql::query::select::window::FrameBound;
+    ///
+    /// #[derive(Table)]
+    /// struct Payment {
+    ///     id: i64,
+    ///     account_id: i64,
+    ///     amount: i64
+    /// }
+    ///
+    /// let stmt = Payment::table().select_with(|payment| {
+    ///     (
+    ///         payment.id,
+    ///         sum(payment.amount).over(
+    ///             window()
+    ///                 .partition_by(payment.account_id)
+    ///                 .order_by(payment.id)
+    ///                 .rows_between(FrameBound::UnboundedPreceding, FrameBound::CurrentRow),
+    ///         ),
+    ///     )
+    /// });
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT payments.id,CAST(SUM(payments.amount) OVER (\
+    ///     PARTITION BY payments.account_id ORDER BY payments.id \
+    ///     ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW\
+    ///     ) AS BIGINT) FROM payments;"
+    /// );
+    /// ```
+    pub fn over<P, O>(self, window: Window<P, O>) -> Over<Self, P, O> {
+        Over::new(self, window)
+    }
+}
+
+macro_rules! impl_ranking {
+    ($($ty:ident($name:literal)),*) => {
+        $(
+            pub struct $ty;
+
+            impl WindowFunction for $ty {
+                type Output = i64;
+
+                fn write_function<D: Dialect>(&self, sql: &mut Writer<D>) {
+                    sql.push_str($name);
+                    sql.push_str("()");
+                }
+            }
+
+            impl $ty {
+                pub fn over<P, O>(self, window: Window<P, O>) -> Over<Self, P, O> {
+                    Over::new(self, window)
+                }
+            }
+        )*
+    };
+}
+
+impl_ranking!(
+    RowNumber("ROW_NUMBER"),
+    Rank("RANK"),
+    DenseRank("DENSE_RANK")
+);
+
+/// Numbers the rows of each partition, starting at 1.
+/// ```
+/// use typed_sql::{Query, Table, ToSql};
+/// use typed_sql::query::{row_number, window};
+///
+/// #[derive(Table)]
+/// struct Post {
+///     id: i64,
+///     user_id: i64
+/// }
+///
+/// let stmt = Post::table().select_with(|post| {
+///     (
+///         post.id,
+///         row_number().over(window().partition_by(post.user_id).order_by(post.id.descending())),
+///     )
+/// });
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "SELECT posts.id,ROW_NUMBER() OVER (\
+///     PARTITION BY posts.user_id ORDER BY posts.id DESC\
+///     ) FROM posts;"
+/// );
+/// ```
+pub fn row_number() -> RowNumber {
+    RowNumber
+}
+
+/// Ranks the rows of each partition by their order, with gaps after ties.
+pub fn rank() -> Rank {
+    Rank
+}
+
+/// Ranks the rows of each partition by their order, without gaps after ties.
+pub fn dense_rank() -> DenseRank {
+    DenseRank
+}
+
+/// A value from another row of the partition, which is `NULL` past its edges.
+pub struct Shift<T, A> {
+    function: &'static str,
+    field: Field<T, A>,
+    offset: usize,
+}
+
+impl<T: Table, A: Nullable> WindowFunction for Shift<T, A> {
+    type Output = A::Output;
+
+    fn write_function<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str(self.function);
+        sql.push('(');
+        self.field.write_field(sql);
+        sql.write_fmt(format_args!(", {})", self.offset)).unwrap();
+    }
+}
+
+impl<T: Table, A: Nullable> Shift<T, A> {
+    pub fn over<P, O>(self, window: Window<P, O>) -> Over<Self, P, O> {
+        Over::new(self, window)
+    }
+}
+
+/// The value of `field` from `offset` rows before the current row.
+/// ```
+/// use typed_sql::{Query, Table, ToSql};
+/// use typed_sql::query::{lag, window};
+///
+/// #[derive(Table)]
+/// struct Price {
+///     day: i64,
+///     close: f64
+/// }
+///
+/// let stmt = Price::table()
+///     .select_with(|price| (price.day, lag(price.close, 1).over(window().order_by(price.day))));
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "SELECT prices.day,LAG(prices.close, 1) OVER (ORDER BY prices.day) FROM prices;"
+/// );
+/// ```
+pub fn lag<T: Table, A: Nullable>(field: Field<T, A>, offset: usize) -> Shift<T, A> {
+    Shift {
+        function: "LAG",
+        field,
+        offset,
+    }
+}
+
+/// The value of `field` from `offset` rows after the current row.
+pub fn lead<T: Table, A: Nullable>(field: Field<T, A>, offset: usize) -> Shift<T, A> {
+    Shift {
+        function: "LEAD",
+        field,
+        offset,
+    }
+}
+
+/// An edge of the frame of rows a window function is computed over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(usize),
+    CurrentRow,
+    Following(usize),
+    UnboundedFollowing,
+}
+
+impl FrameBound {
+    fn write_bound<D: Dialect>(self, sql: &mut Writer<D>) {
+        match self {
+            FrameBound::UnboundedPreceding => sql.push_str("UNBOUNDED PRECEDING"),
+            FrameBound::Preceding(n) => sql.write_fmt(format_args!("{} PRECEDING", n)).unwrap(),
+            FrameBound::CurrentRow => sql.push_str("CURRENT ROW"),
+            FrameBound::Following(n) => sql.write_fmt(format_args!("{} FOLLOWING", n)).unwrap(),
+            FrameBound::UnboundedFollowing => sql.push_str("UNBOUNDED FOLLOWING"),
+        }
+    }
+}
+
+struct Frame {
+    units: &'static str,
+    start: FrameBound,
+    end: FrameBound,
+}
+
+/// The rows a window function is computed over.
+pub struct Window<P, O> {
+    partition: P,
+    order: O,
+    frame: Option<Frame>,
+}
+
+/// Creates a window over every row, which can be partitioned and ordered.
+pub fn window() -> Window<(), ()> {
+    Window {
+        partition: (),
+        order: (),
+        frame: None,
+    }
+}
+
+impl<O> Window<(), O> {
+    /// Computes the function separately for each group of rows with equal `columns`.
+    pub fn partition_by<P: GroupOrder>(self, columns: P) -> Window<P, O> {
+        Window {
+            partition: columns,
+            order: self.order,
+            frame: self.frame,
+        }
+    }
+}
+
+impl<P> Window<P, ()> {
+    pub fn order_by<O: Order>(self, order: O) -> Window<P, O> {
+        Window {
+            partition: self.partition,
+            order,
+            frame: self.frame,
+        }
+    }
+}
+
+impl<P, O> Window<P, O> {
+    /// Limits the frame to the rows between `start` and `end`, counted by position.
+    pub fn rows_between(self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame("ROWS", start, end)
+    }
+
+    /// Limits the frame to the rows between `start` and `end`, counted by the value of the order.
+    pub fn range_between(self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame("RANGE", start, end)
+    }
+
+    fn frame(self, units: &'static str, start: FrameBound, end: FrameBound) -> Self {
+        Self {
+            frame: Some(Frame { units, start, end }),
+            ..self
+        }
+    }
+}
+
+/// The columns of a `PARTITION BY`, which is omitted for `()`.
+pub trait Partition {
+    const IS_EMPTY: bool = false;
+
+    fn write_partition<D: Dialect>(&self, sql: &mut Writer<D>);
+}
+
+impl Partition for () {
+    const IS_EMPTY: bool = true;
+
+    fn write_partition<D: Dialect>(&self, _sql: &mut Writer<D>) {}
+}
+
+impl<G: GroupOrder> Partition for G {
+    fn write_partition<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("PARTITION BY ");
+        self.write_columns(sql);
+    }
+}
+
+/// The order of an `ORDER BY` in a window, which is omitted for `()`.
+pub trait WindowOrder {
+    const IS_EMPTY: bool = false;
+
+    fn write_window_order<D: Dialect>(&self, sql: &mut Writer<D>);
+}
+
+impl WindowOrder for () {
+    const IS_EMPTY: bool = true;
+
+    fn write_window_order<D: Dialect>(&self, _sql: &mut Writer<D>) {}
+}
+
+impl<O: Order> WindowOrder for O {
+    fn write_window_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("ORDER BY ");
+        self.write_order(sql);
+    }
+}
+
+/// A window function computed over a window.
+pub struct Over<F, P, O> {
+    function: F,
+    window: Window<P, O>,
+}
+
+impl<F, P, O> Over<F, P, O> {
+    pub(crate) fn new(function: F, window: Window<P, O>) -> Self {
+        Self { function, window }
+    }
+}
+
+impl<F, P, O> WriteQueryable for Over<F, P, O>
+where
+    F: WindowFunction,
+    P: Partition,
+    O: WindowOrder,
+{
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.function
+            .write_over(sql, |sql| self.window.write_window(sql));
+    }
+}
+
+impl<P: Partition, O: WindowOrder> Window<P, O> {
+    fn write_window<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str(" OVER (");
+
+        self.partition.write_partition(sql);
+
+        if !O::IS_EMPTY {
+            if !P::IS_EMPTY {
+                sql.push(' ');
+            }
+            self.order.write_window_order(sql);
+        }
+
+        if let Some(frame) = &self.frame {
+            if !P::IS_EMPTY || !O::IS_EMPTY {
+                sql.push(' ');
+            }
+            sql.push_str(frame.units);
+            sql.push_str(" BETWEEN ");
+            frame.start.write_bound(sql);
+            sql.push_str(" AND ");
+            frame.end.write_bound(sql);
+        }
+
+        sql.push(')');
+    }
+}
+
+impl<F, P, O> Projection for Over<F, P, O>
+where
+    F: WindowFunction,
+    P: Partition,
+    O: WindowOrder,
+{
+    type Output = F::Output;
+}
+
+impl<F, P, O> Scalar for Over<F, P, O>
+where
+    F: WindowFunction,
+    P: Partition,
+    O: WindowOrder,
+{
+}
+
+impl<F: CheckedSql, P, O> CheckedSql for Over<F, P, O> {}
+
+impl CheckedSql for RowNumber {}
+
+impl CheckedSql for Rank {}
+
+impl CheckedSql for DenseRank {}
+
+impl<T, A> CheckedSql for Shift<T, A> {}
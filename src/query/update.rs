@@ -1,28 +1,21 @@
-use super::predicate::{And, Eq, Op, Predicate};
-use crate::types::{Bind, Primitive};
-use crate::{CheckedSql, Table, ToSql};
+use super::predicate::{And, Compare, Eq};
+use crate::dialect::Dialect;
+use crate::types::{Expr, Field};
+use crate::{CheckedSql, Table, ToSql, Writer};
 use std::marker::PhantomData;
 
 pub trait UpdateSet {
-    fn write_set(&self, sql: &mut String);
+    fn write_set<D: Dialect>(&self, sql: &mut Writer<D>);
 }
 
-impl<T, A> UpdateSet for Op<T, A, Bind, Eq>
+impl<T, A, U> UpdateSet for Compare<Field<T, A>, A, U, Eq>
 where
     T: Table,
+    U: Expr<A>,
 {
-    fn write_set(&self, sql: &mut String) {
-        self.write_predicate(sql);
-    }
-}
-
-impl<T, A, U> UpdateSet for Op<T, A, U, Eq>
-where
-    T: Table,
-    U: Primitive,
-{
-    fn write_set(&self, sql: &mut String) {
-        self.write_predicate(sql);
+    fn write_set<D: Dialect>(&self, sql: &mut Writer<D>) {
+        write_set_column(&self.lhs, sql);
+        self.rhs.write_expr(sql);
     }
 }
 
@@ -31,13 +24,22 @@ where
     H: UpdateSet,
     T: UpdateSet,
 {
-    fn write_set(&self, sql: &mut String) {
+    fn write_set<D: Dialect>(&self, sql: &mut Writer<D>) {
         self.head.write_set(sql);
         sql.push(',');
         self.tail.write_set(sql);
     }
 }
 
+fn write_set_column<T: Table, A, D: Dialect>(column: &Field<T, A>, sql: &mut Writer<D>) {
+    if D::QUALIFY_SET {
+        column.write_field(sql);
+    } else {
+        column.write_column(sql);
+    }
+    sql.push_str(" = ");
+}
+
 pub struct Update<T: ?Sized, S> {
     _table: PhantomData<T>,
     set: S,
@@ -57,9 +59,9 @@ where
     T: Table + ?Sized,
     S: UpdateSet,
 {
-    fn write_sql_unchecked(&self, sql: &mut String) {
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
         sql.push_str("UPDATE ");
-        sql.push_str(T::NAME);
+        T::write_table(sql);
         sql.push_str(" SET ");
         self.set.write_set(sql);
     }
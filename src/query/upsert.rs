@@ -0,0 +1,308 @@
+use super::insert::{InsertRows, InsertStatement};
+use super::update::UpdateSet;
+use crate::dialect::Dialect;
+use crate::table::{Alias, Aliasable};
+use crate::types::field::{Field, Then};
+use crate::{CheckedSql, Table, ToSql, Writer};
+use std::marker::PhantomData;
+
+/// The row an upsert tried to insert, whose fields are written as `excluded.column`,
+/// or `VALUES(column)` in MySQL.
+pub struct Excluded;
+
+impl Alias for Excluded {
+    const NAME: &'static str = "excluded";
+
+    fn write_qualified<D: Dialect>(sql: &mut Writer<D>, column: &str) {
+        D::write_excluded(sql, column);
+    }
+}
+
+/// The unique columns whose conflicts are handled by an upsert, which is any of them for `()`.
+pub trait ConflictTarget {
+    const IS_EMPTY: bool = false;
+
+    fn write_target<D: Dialect>(&self, sql: &mut Writer<D>);
+}
+
+impl ConflictTarget for () {
+    const IS_EMPTY: bool = true;
+
+    fn write_target<D: Dialect>(&self, _sql: &mut Writer<D>) {}
+}
+
+impl<T: Table, A> ConflictTarget for Field<T, A> {
+    fn write_target<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_column(sql);
+    }
+}
+
+impl<H, T> ConflictTarget for Then<H, T>
+where
+    H: ConflictTarget,
+    T: ConflictTarget,
+{
+    fn write_target<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.head.write_target(sql);
+        sql.push(',');
+        self.tail.write_target(sql);
+    }
+}
+
+/// What an upsert does with a row that conflicts with an existing one.
+pub trait ConflictAction {
+    /// Whether the existing row is left as it is.
+    const IS_NOTHING: bool;
+
+    fn write_set<D: Dialect>(&self, sql: &mut Writer<D>);
+}
+
+/// Leaves the existing row as it is.
+pub struct DoNothing;
+
+impl ConflictAction for DoNothing {
+    const IS_NOTHING: bool = true;
+
+    fn write_set<D: Dialect>(&self, _sql: &mut Writer<D>) {}
+}
+
+/// Updates the existing row with `set`.
+pub struct DoUpdate<S> {
+    set: S,
+}
+
+impl<S: UpdateSet> ConflictAction for DoUpdate<S> {
+    const IS_NOTHING: bool = false;
+
+    fn write_set<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.set.write_set(sql);
+    }
+}
+
+impl<T: Table, V> InsertStatement<T, V> {
+    /// Handles rows that conflict with existing ones on the unique `target` columns.
+    ///
+    /// MySQL can't choose the columns, so `target` isn't written
+    /// and a conflict on any unique key is handled.
+    /// SQL Server upserts with `MERGE`, which isn't supported.
+    /// ```
+    /// use typed_sql::{Comparable, Insertable, Query, Table, ToSql};
+    /// use typed_sql::dialect::MySql;
+    ///
+    /// #[derive(Table, Insertable)]
+    /// struct User {
+    ///     email: String,
+    ///     name: String
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .insert(User {
+    ///         email: String::from("ann@example.com"),
+    ///         name: String::from("Ann"),
+    ///     })
+    ///     .on_conflict(|user| user.email)
+    ///     .do_update(|user, excluded| user.name.eq(excluded.name));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "INSERT INTO users(email,name) VALUES ('ann@example.com','Ann') \
+    ///     ON CONFLICT (email) DO UPDATE SET name = excluded.name;"
+    /// );
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<MySql>(),
+    ///     "INSERT INTO users(email,name) VALUES ('ann@example.com','Ann') \
+    ///     ON DUPLICATE KEY UPDATE users.name = VALUES(name);"
+    /// );
+    ///
+    /// let stmt = User::table()
+    ///     .insert(User {
+    ///         email: String::from("ann@example.com"),
+    ///         name: String::from("Ann"),
+    ///     })
+    ///     .on_conflict(|user| user.email)
+    ///     .do_nothing();
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "INSERT INTO users(email,name) VALUES ('ann@example.com','Ann') \
+    ///     ON CONFLICT (email) DO NOTHING;"
+    /// );
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<MySql>(),
+    ///     "INSERT IGNORE INTO users(email,name) VALUES ('ann@example.com','Ann');"
+    /// );
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use typed_sql::{Insertable, Query, Table, ToSql};
+    /// use typed_sql::dialect::MsSql;
+    ///
+    /// #[derive(Table, Insertable)]
+    /// struct User {
+    ///     email: String
+    /// }
+    ///
+    /// User::table()
+    ///     .insert(User { email: String::from("ann@example.com") })
+    ///     .on_conflict(|user| user.email)
+    ///     .do_nothing()
+    ///     .to_sql_for::<MsSql>();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use typed_sql::{Comparable, Insertable, Query, Table};
+    ///
+    /// #[derive(Table, Insertable)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String
+    /// }
+    ///
+    /// User::table()
+    ///     .insert(User { id: 1, name: String::from("Ann") })
+    ///     .on_conflict(|user| user.id)
+    ///     .do_update(|user, excluded| user.name.eq(excluded.id));
+    /// ```
+    pub fn on_conflict<F, C>(self, f: F) -> OnConflict<T, V, C>
+    where
+        F: FnOnce(T::Fields) -> C,
+        C: ConflictTarget,
+    {
+        OnConflict {
+            insert: self,
+            target: f(Default::default()),
+        }
+    }
+
+    /// Skips rows that conflict with existing ones on any unique key.
+    ///
+    /// This is `INSERT IGNORE` in MySQL, which also turns other errors into warnings,
+    /// such as truncated values or missing foreign keys.
+    /// ```
+    /// use typed_sql::{Insertable, Query, Table, ToSql};
+    /// use typed_sql::dialect::MySql;
+    ///
+    /// #[derive(Table, Insertable)]
+    /// struct Tag {
+    ///     name: String
+    /// }
+    ///
+    /// let stmt = Tag::table().insert(Tag { name: String::from("rust") }).ignore();
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "INSERT INTO tags(name) VALUES ('rust') ON CONFLICT DO NOTHING;"
+    /// );
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql_for::<MySql>(),
+    ///     "INSERT IGNORE INTO tags(name) VALUES ('rust');"
+    /// );
+    /// ```
+    pub fn ignore(self) -> Upsert<T, V, (), DoNothing> {
+        Upsert {
+            insert: self,
+            target: (),
+            action: DoNothing,
+        }
+    }
+}
+
+/// An insert with the columns of its conflicts, which is finished by
+/// [`do_update`](OnConflict::do_update) or [`do_nothing`](OnConflict::do_nothing).
+pub struct OnConflict<T, V, C> {
+    insert: InsertStatement<T, V>,
+    target: C,
+}
+
+impl<T: Table, V, C: ConflictTarget> OnConflict<T, V, C> {
+    /// Updates each conflicting row with the `set` made from its fields
+    /// and the fields of the row that was excluded.
+    pub fn do_update<F, S>(self, f: F) -> Upsert<T, V, C, DoUpdate<S>>
+    where
+        T: Aliasable<Excluded>,
+        F: FnOnce(<T as Table>::Fields, <T as Aliasable<Excluded>>::Fields) -> S,
+        S: UpdateSet,
+    {
+        let set = f(Default::default(), Default::default());
+        Upsert {
+            insert: self.insert,
+            target: self.target,
+            action: DoUpdate { set },
+        }
+    }
+
+    /// Skips the conflicting rows.
+    ///
+    /// This is `INSERT IGNORE` in MySQL, which skips conflicts on any unique key
+    /// and also turns other errors into warnings, such as truncated values or missing foreign keys.
+    pub fn do_nothing(self) -> Upsert<T, V, C, DoNothing> {
+        Upsert {
+            insert: self.insert,
+            target: self.target,
+            action: DoNothing,
+        }
+    }
+}
+
+/// An insert that updates or skips the rows that conflict with existing ones.
+///
+/// This fails to compile for dialects without upserts, such as SQL Server.
+pub struct Upsert<T, V, C, A> {
+    insert: InsertStatement<T, V>,
+    target: C,
+    action: A,
+}
+
+impl<T, V, C, A> ToSql for Upsert<T, V, C, A>
+where
+    T: Table,
+    V: InsertRows,
+    C: ConflictTarget,
+    A: ConflictAction,
+{
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
+        let () = AssertUpsert::<D>::OK;
+
+        if D::ON_CONFLICT {
+            self.insert.write_sql_unchecked(sql);
+            sql.push_str(" ON CONFLICT");
+            if !C::IS_EMPTY {
+                sql.push_str(" (");
+                self.target.write_target(sql);
+                sql.push(')');
+            }
+            if A::IS_NOTHING {
+                sql.push_str(" DO NOTHING");
+            } else {
+                sql.push_str(" DO UPDATE SET ");
+                self.action.write_set(sql);
+            }
+        } else {
+            self.insert.write_insert(sql, A::IS_NOTHING);
+            if !A::IS_NOTHING {
+                sql.push_str(" ON DUPLICATE KEY UPDATE ");
+                self.action.write_set(sql);
+            }
+        }
+    }
+}
+
+struct AssertUpsert<D>(PhantomData<D>);
+
+impl<D: Dialect> AssertUpsert<D> {
+    const OK: () = assert!(D::UPSERT, "this dialect can't upsert rows");
+}
+
+impl<T, V, C, A> CheckedSql for Upsert<T, V, C, A>
+where
+    InsertStatement<T, V>: CheckedSql,
+    A: CheckedSql,
+{
+}
+
+impl CheckedSql for DoNothing {}
+
+impl<S: CheckedSql> CheckedSql for DoUpdate<S> {}
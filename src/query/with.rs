@@ -0,0 +1,352 @@
+use super::select::compound::{SameRows, UnionAll};
+use super::select::queryable::Rows;
+use super::select::{Compound, Select};
+use crate::dialect::Dialect;
+use crate::table::{Alias, Aliasable, Columns, TableQuery};
+use crate::{CheckedSql, Table, ToSql, Writer};
+use std::marker::PhantomData;
+
+/// The rows of a common table expression named by `A`, with the columns of the table `T`.
+///
+/// Its fields are qualified by the name of the expression,
+/// so it can be selected, filtered and joined like any other table.
+pub struct Cte<T, A> {
+    _table: PhantomData<T>,
+    _alias: PhantomData<A>,
+}
+
+impl<T, A> Table for Cte<T, A>
+where
+    T: Aliasable<A>,
+    A: Alias,
+{
+    const NAME: &'static str = A::NAME;
+
+    type Fields = <T as Aliasable<A>>::Fields;
+
+    type NullableFields = <T as Aliasable<A>>::NullableFields;
+}
+
+/// A list of common table expressions written before a statement.
+pub trait CommonTables {
+    const IS_EMPTY: bool;
+
+    fn is_recursive(&self) -> bool;
+
+    fn write_tables<D: Dialect>(&self, sql: &mut Writer<D>);
+}
+
+impl CommonTables for () {
+    const IS_EMPTY: bool = true;
+
+    fn is_recursive(&self) -> bool {
+        false
+    }
+
+    fn write_tables<D: Dialect>(&self, _sql: &mut Writer<D>) {}
+}
+
+/// Names the rows of `select` as `name` for the following statement.
+///
+/// ```
+/// use typed_sql::{Comparable, Query, Table, ToSql};
+/// use typed_sql::query::with;
+/// use typed_sql::table::Alias;
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64,
+///     active: bool
+/// }
+///
+/// struct Active;
+///
+/// impl Alias for Active {
+///     const NAME: &'static str = "active_users";
+/// }
+///
+/// let stmt = with(Active, User::table().select().filter(|user| user.active.eq(true)))
+///     .query(|active| active.select().filter(|user| user.id.gt(10)));
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "WITH active_users AS (SELECT * FROM users WHERE users.active = TRUE) \
+///     SELECT * FROM active_users WHERE active_users.id > 10;"
+/// );
+/// ```
+pub fn with<A, S, T>(name: A, select: S) -> With<T, A, S, ()>
+where
+    A: Alias,
+    S: Select,
+    S::Queryable: Rows<S::Selectable, Row = T>,
+    T: Aliasable<A>,
+{
+    With::new(name, select, false, false, ())
+}
+
+/// Names the rows of `select` as `name` with the columns of `T`,
+/// which are read from the select list in order, so a projection or aggregate can be named.
+///
+/// ```
+/// use typed_sql::{Comparable, Query, Table, ToSql};
+/// use typed_sql::query::{sum, with_columns};
+/// use typed_sql::table::Alias;
+///
+/// #[derive(Table)]
+/// struct Order {
+///     customer_id: i64,
+///     amount: i64
+/// }
+///
+/// #[derive(Table)]
+/// struct Total {
+///     customer_id: i64,
+///     amount: Option<i64>
+/// }
+///
+/// struct Totals;
+///
+/// impl Alias for Totals {
+///     const NAME: &'static str = "totals";
+/// }
+///
+/// let totals = Order::table()
+///     .select_with(|order| (order.customer_id, sum(order.amount)))
+///     .group_by(|order| order.customer_id);
+///
+/// let stmt = with_columns::<Total, _, _>(Totals, totals)
+///     .query(|totals| totals.select().filter(|total| total.amount.gt(100)));
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "WITH totals(customer_id,amount) AS (\
+///     SELECT orders.customer_id,CAST(SUM(orders.amount) AS BIGINT) FROM orders \
+///     GROUP BY orders.customer_id\
+///     ) SELECT * FROM totals WHERE totals.amount > 100;"
+/// );
+/// ```
+pub fn with_columns<T, A, S>(name: A, select: S) -> With<T, A, S, ()>
+where
+    A: Alias,
+    S: Select,
+    S::Queryable: Rows<S::Selectable>,
+    T: Aliasable<A> + Columns<Types = <S::Queryable as Rows<S::Selectable>>::Row>,
+{
+    With::new(name, select, true, false, ())
+}
+
+/// Names the rows of `anchor` combined with the rows of `recursive` as `name`,
+/// where `recursive` joins [`Cte`] to walk a tree of rows.
+///
+/// ```
+/// use typed_sql::{Comparable, Join, Query, Table, ToSql};
+/// use typed_sql::query::with_recursive;
+/// use typed_sql::query::with::Cte;
+/// use typed_sql::query::Joined;
+/// use typed_sql::table::Alias;
+///
+/// #[derive(Table)]
+/// struct Node {
+///     id: i64,
+///     parent_id: Option<i64>
+/// }
+///
+/// struct Tree;
+///
+/// impl Alias for Tree {
+///     const NAME: &'static str = "tree";
+/// }
+///
+/// #[derive(Join)]
+/// struct Child {
+///     node: Node,
+///     parent: Cte<Node, Tree>
+/// }
+///
+/// let children = Child::join(|join| ChildJoin {
+///     parent: Joined::new(join.node.parent_id.eq(join.parent.id)),
+/// })
+/// .select_with(|join| join.node);
+///
+/// let stmt = with_recursive(
+///     Tree,
+///     Node::table().select().filter(|node| node.id.eq(1)),
+///     children,
+/// )
+/// .query(|tree| tree.select());
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "WITH RECURSIVE tree AS (\
+///     SELECT * FROM nodes WHERE nodes.id = 1 \
+///     UNION ALL \
+///     SELECT nodes.id,nodes.parent_id FROM nodes \
+///     INNER JOIN tree ON nodes.parent_id = tree.id\
+///     ) SELECT * FROM tree;"
+/// );
+/// ```
+pub fn with_recursive<A, S, R, T>(
+    name: A,
+    anchor: S,
+    recursive: R,
+) -> With<T, A, Compound<S, R, UnionAll>, ()>
+where
+    A: Alias,
+    S: Select + SameRows<R>,
+    S::Queryable: Rows<S::Selectable, Row = T>,
+    R: Select,
+    T: Aliasable<A>,
+{
+    With::new(name, Compound::new(anchor, recursive), false, true, ())
+}
+
+pub struct With<T, A, S, P> {
+    select: S,
+    has_columns: bool,
+    is_recursive: bool,
+    prev: P,
+    _table: PhantomData<T>,
+    _alias: PhantomData<A>,
+}
+
+impl<T, A, S, P> With<T, A, S, P> {
+    fn new(_name: A, select: S, has_columns: bool, is_recursive: bool, prev: P) -> Self {
+        Self {
+            select,
+            has_columns,
+            is_recursive,
+            prev,
+            _table: PhantomData,
+            _alias: PhantomData,
+        }
+    }
+}
+
+impl<T, A, S, P> With<T, A, S, P>
+where
+    T: Aliasable<A>,
+    A: Alias,
+    S: Select,
+    P: CommonTables,
+{
+    /// Adds another common table expression, which can refer to the previous ones.
+    pub fn with<A2, S2, T2>(self, name: A2, select: S2) -> With<T2, A2, S2, Self>
+    where
+        A2: Alias,
+        S2: Select,
+        S2::Queryable: Rows<S2::Selectable, Row = T2>,
+        T2: Aliasable<A2>,
+    {
+        With::new(name, select, false, false, self)
+    }
+
+    /// Adds another common table expression with the columns of `T2`,
+    /// which are read from the select list in order.
+    pub fn with_columns<T2, A2, S2>(self, name: A2, select: S2) -> With<T2, A2, S2, Self>
+    where
+        A2: Alias,
+        S2: Select,
+        S2::Queryable: Rows<S2::Selectable>,
+        T2: Aliasable<A2> + Columns<Types = <S2::Queryable as Rows<S2::Selectable>>::Row>,
+    {
+        With::new(name, select, true, false, self)
+    }
+
+    /// Adds another common table expression that refers to itself.
+    pub fn with_recursive<A2, S2, R2, T2>(
+        self,
+        name: A2,
+        anchor: S2,
+        recursive: R2,
+    ) -> With<T2, A2, Compound<S2, R2, UnionAll>, Self>
+    where
+        A2: Alias,
+        S2: Select + SameRows<R2>,
+        S2::Queryable: Rows<S2::Selectable, Row = T2>,
+        R2: Select,
+        T2: Aliasable<A2>,
+    {
+        With::new(name, Compound::new(anchor, recursive), false, true, self)
+    }
+
+    /// Creates the statement that uses these expressions from the last one's table.
+    pub fn query<F, Q>(self, f: F) -> WithQuery<Self, Q>
+    where
+        F: FnOnce(TableQuery<Cte<T, A>>) -> Q,
+        Q: ToSql,
+    {
+        let stmt = f(Cte::table());
+        WithQuery { tables: self, stmt }
+    }
+}
+
+impl<T, A, S, P> CommonTables for With<T, A, S, P>
+where
+    T: Columns,
+    A: Alias,
+    S: Select,
+    P: CommonTables,
+{
+    const IS_EMPTY: bool = false;
+
+    fn is_recursive(&self) -> bool {
+        self.is_recursive || self.prev.is_recursive()
+    }
+
+    fn write_tables<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.prev.write_tables(sql);
+        if !P::IS_EMPTY {
+            sql.push(',');
+        }
+        sql.write_ident(A::NAME);
+        if self.has_columns {
+            sql.push('(');
+            T::write_columns(sql);
+            sql.push(')');
+        }
+        sql.push_str(" AS (");
+        self.select.write_sql_unchecked(sql);
+        sql.push(')');
+    }
+}
+
+impl<T, A, S: CheckedSql, P: CheckedSql> CheckedSql for With<T, A, S, P> {}
+
+/// A statement that's preceded by common table expressions.
+pub struct WithQuery<W, Q> {
+    tables: W,
+    stmt: Q,
+}
+
+impl<W, Q> Select for WithQuery<W, Q>
+where
+    W: CommonTables,
+    Q: Select,
+{
+    type Selectable = Q::Selectable;
+    type Queryable = Q::Queryable;
+
+    const IS_COMPOUND: bool = true;
+
+    const IS_ORDERED: bool = Q::IS_ORDERED;
+}
+
+impl<W, Q> ToSql for WithQuery<W, Q>
+where
+    W: CommonTables,
+    Q: ToSql,
+{
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>) {
+        if self.tables.is_recursive() {
+            sql.push_str(D::WITH_RECURSIVE);
+        } else {
+            sql.push_str("WITH");
+        }
+        sql.push(' ');
+        self.tables.write_tables(sql);
+        sql.push(' ');
+        self.stmt.write_sql_unchecked(sql);
+    }
+}
+
+impl<W: CheckedSql, Q: CheckedSql> CheckedSql for WithQuery<W, Q> {}
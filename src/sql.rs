@@ -1,3 +1,9 @@
+use crate::dialect::{Dialect, Postgres};
+use crate::types::{Primitive, Value};
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+
 pub trait CheckedSql {}
 
 impl CheckedSql for i8 {}
@@ -16,10 +22,129 @@ impl CheckedSql for u32 {}
 
 impl CheckedSql for u64 {}
 
+impl CheckedSql for isize {}
+
+impl CheckedSql for usize {}
+
+impl CheckedSql for f32 {}
+
+impl CheckedSql for f64 {}
+
+impl CheckedSql for bool {}
+
+impl CheckedSql for char {}
+
+impl CheckedSql for [u8] {}
+
+impl CheckedSql for Vec<u8> {}
+
+/// Strings are escaped by the dialect they're written in.
+impl CheckedSql for str {}
+
+impl CheckedSql for String {}
+
+impl CheckedSql for Cow<'_, str> {}
+
+impl<T: CheckedSql + ?Sized> CheckedSql for &T {}
+
+impl<T: CheckedSql> CheckedSql for Option<T> {}
+
+impl CheckedSql for () {}
+
+impl<A: CheckedSql, B: CheckedSql> CheckedSql for (A, B) {}
+
+/// A SQL buffer written in the dialect `D`.
+#[derive(Debug)]
+pub struct Writer<D> {
+    sql: String,
+    params: Option<Vec<Value>>,
+    _dialect: PhantomData<D>,
+}
+
+impl<D: Dialect> Writer<D> {
+    pub fn new() -> Self {
+        Self {
+            sql: String::new(),
+            params: None,
+            _dialect: PhantomData,
+        }
+    }
+
+    /// Creates a writer that binds values as parameters instead of writing them inline.
+    pub fn parameterized() -> Self {
+        Self {
+            params: Some(Vec::new()),
+            ..Self::new()
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.sql.push(c);
+    }
+
+    pub fn push_str(&mut self, s: &str) {
+        self.sql.push_str(s);
+    }
+
+    pub fn write_ident(&mut self, ident: &str) {
+        D::write_ident(self, ident);
+    }
+
+    pub fn write_str(&mut self, s: &str) {
+        D::write_str(self, s);
+    }
+
+    pub fn write_placeholder(&mut self, n: usize) {
+        D::write_placeholder(self, n);
+    }
+
+    /// Writes with `f`, inlining values even if this writer is parameterized.
+    pub(crate) fn write_inline<F: FnOnce(&mut Self)>(&mut self, f: F) {
+        let params = self.params.take();
+        f(self);
+        self.params = params;
+    }
+
+    /// Writes `value` inline, or as a placeholder if this writer is parameterized.
+    pub fn write_value<P: Primitive>(&mut self, value: &P) {
+        if let Some(params) = &mut self.params {
+            params.push(match value.to_value() {
+                Value::Float(float) if !float.is_finite() => D::bind_non_finite(float),
+                value => value,
+            });
+            let n = params.len();
+            self.write_placeholder(n);
+        } else {
+            value.write_primative(self);
+        }
+    }
+
+    pub fn into_string(self) -> String {
+        self.sql
+    }
+
+    pub fn into_parts(self) -> (String, Vec<Value>) {
+        (self.sql, self.params.unwrap_or_default())
+    }
+}
+
+impl<D: Dialect> Default for Writer<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D> fmt::Write for Writer<D> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.sql.push_str(s);
+        Ok(())
+    }
+}
+
 pub trait ToSql {
-    fn write_sql_unchecked(&self, sql: &mut String);
+    fn write_sql_unchecked<D: Dialect>(&self, sql: &mut Writer<D>);
 
-    fn write_sql(&self, sql: &mut String)
+    fn write_sql<D: Dialect>(&self, sql: &mut Writer<D>)
     where
         Self: CheckedSql,
     {
@@ -27,16 +152,78 @@ pub trait ToSql {
     }
 
     fn to_sql_unchecked(&self) -> String {
-        let mut sql = String::new();
+        self.to_sql_unchecked_for::<Postgres>()
+    }
+
+    fn to_sql(&self) -> String
+    where
+        Self: CheckedSql,
+    {
+        self.to_sql_for::<Postgres>()
+    }
+
+    fn to_sql_unchecked_for<D: Dialect>(&self) -> String {
+        let mut sql = Writer::<D>::new();
         self.write_sql_unchecked(&mut sql);
         sql.push(';');
-        sql
+        sql.into_string()
     }
 
-    fn to_sql(&self) -> String
+    /// Serializes this statement with every value bound as a parameter.
+    ///
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    /// use typed_sql::types::Value;
+    ///
+    /// #[derive(Table)]
+    /// struct Post {
+    ///     id: i64,
+    ///     content: String
+    /// }
+    ///
+    /// let stmt = Post::table()
+    ///     .select()
+    ///     .filter(|post| post.content.eq("untrusted").and(post.id.gt(2)));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_parameterized(),
+    ///     (
+    ///         String::from("SELECT * FROM posts WHERE posts.content = $1 AND posts.id > $2;"),
+    ///         vec![Value::Text(String::from("untrusted")), Value::Int(2)]
+    ///     )
+    /// );
+    /// ```
+    fn to_parameterized(&self) -> (String, Vec<Value>) {
+        self.to_parameterized_for::<Postgres>()
+    }
+
+    fn to_parameterized_for<D: Dialect>(&self) -> (String, Vec<Value>) {
+        let mut sql = Writer::<D>::parameterized();
+        self.write_sql_unchecked(&mut sql);
+        sql.push(';');
+        sql.into_parts()
+    }
+
+    /// Serializes this statement in the dialect `D`.
+    ///
+    /// ```
+    /// use typed_sql::{Comparable, Query, Table, ToSql};
+    /// use typed_sql::dialect::{MySql, Postgres};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64
+    /// }
+    ///
+    /// let stmt = User::table().update(|user| user.id.eq(2));
+    ///
+    /// assert_eq!(stmt.to_sql_for::<Postgres>(), "UPDATE users SET id = 2;");
+    /// assert_eq!(stmt.to_sql_for::<MySql>(), "UPDATE users SET users.id = 2;");
+    /// ```
+    fn to_sql_for<D: Dialect>(&self) -> String
     where
         Self: CheckedSql,
     {
-        self.to_sql_unchecked()
+        self.to_sql_unchecked_for::<D>()
     }
 }
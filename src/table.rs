@@ -1,3 +1,5 @@
+use crate::dialect::Dialect;
+use crate::Writer;
 use std::marker::PhantomData;
 
 pub trait Table {
@@ -5,9 +7,123 @@ pub trait Table {
 
     type Fields: Default;
 
+    /// The fields of this table when it's outer joined, with every column nullable.
+    type NullableFields: Default;
+
     fn table() -> TableQuery<Self> {
         TableQuery { table: PhantomData }
     }
+
+    /// Writes this table as it's named in `FROM` or `JOIN`.
+    fn write_table<D: Dialect>(sql: &mut Writer<D>) {
+        sql.write_ident(Self::NAME);
+    }
+
+    /// Writes `column` qualified by this table, as it's referred to in expressions.
+    fn write_qualified<D: Dialect>(sql: &mut Writer<D>, column: &str) {
+        sql.write_ident(Self::NAME);
+        sql.push('.');
+        sql.write_ident(column);
+    }
+}
+
+/// A name a table can be referred to by, for joining a table more than once.
+///
+/// ```
+/// use typed_sql::{Comparable, Join, Query, Table, ToSql};
+/// use typed_sql::query::Joined;
+/// use typed_sql::table::{Alias, Aliased};
+///
+/// #[derive(Table)]
+/// struct Employee {
+///     id: i64,
+///     manager_id: Option<i64>
+/// }
+///
+/// struct Mgr;
+///
+/// impl Alias for Mgr {
+///     const NAME: &'static str = "mgr";
+/// }
+///
+/// #[derive(Join)]
+/// struct Report {
+///     employee: Employee,
+///     manager: Aliased<Employee, Mgr>
+/// }
+///
+/// let join = Report::join(|join| ReportJoin {
+///     manager: Joined::new(join.employee.manager_id.eq(join.manager.id)),
+/// });
+///
+/// assert_eq!(
+///     join.select().to_sql(),
+///     "SELECT * FROM employees INNER JOIN employees AS mgr ON employees.manager_id = mgr.id;"
+/// );
+///
+/// let stmt = Employee::table()
+///     .alias::<Mgr>()
+///     .select()
+///     .filter(|mgr| mgr.manager_id.eq(None));
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "SELECT * FROM employees AS mgr WHERE mgr.manager_id IS NULL;"
+/// );
+/// ```
+pub trait Alias {
+    const NAME: &'static str;
+
+    /// Writes `column` qualified by this alias.
+    fn write_qualified<D: Dialect>(sql: &mut Writer<D>, column: &str) {
+        sql.write_ident(Self::NAME);
+        sql.push('.');
+        sql.write_ident(column);
+    }
+}
+
+/// A table whose fields can be qualified by an [`Alias`], implemented by `#[derive(Table)]`.
+pub trait Aliasable<A>: Table {
+    type Fields: Default;
+
+    type NullableFields: Default;
+}
+
+/// The columns of a table in the order they're declared, implemented by `#[derive(Table)]`.
+pub trait Columns: Table {
+    /// The types of the columns as a tuple, or the type itself for a single column.
+    type Types;
+
+    /// Writes the names of the columns separated by commas.
+    fn write_columns<D: Dialect>(sql: &mut Writer<D>);
+}
+
+/// The table `T` referred to by the alias `A`.
+pub struct Aliased<T, A> {
+    _table: PhantomData<T>,
+    _alias: PhantomData<A>,
+}
+
+impl<T, A> Table for Aliased<T, A>
+where
+    T: Aliasable<A>,
+    A: Alias,
+{
+    const NAME: &'static str = A::NAME;
+
+    type Fields = <T as Aliasable<A>>::Fields;
+
+    type NullableFields = <T as Aliasable<A>>::NullableFields;
+
+    fn write_table<D: Dialect>(sql: &mut Writer<D>) {
+        sql.write_ident(T::NAME);
+        sql.push_str(" AS ");
+        sql.write_ident(A::NAME);
+    }
+
+    fn write_qualified<D: Dialect>(sql: &mut Writer<D>, column: &str) {
+        A::write_qualified(sql, column);
+    }
 }
 
 pub trait TableQueryable {
@@ -18,6 +134,16 @@ pub struct TableQuery<T: ?Sized> {
     table: PhantomData<T>,
 }
 
+impl<T: Table> TableQuery<T> {
+    pub fn alias<A>(self) -> TableQuery<Aliased<T, A>>
+    where
+        T: Aliasable<A>,
+        A: Alias,
+    {
+        TableQuery { table: PhantomData }
+    }
+}
+
 impl<T: Table + ?Sized> TableQueryable for TableQuery<T> {
     type Table = T;
 }
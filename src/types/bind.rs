@@ -1,11 +1,23 @@
-use crate::sql::CheckedSql;
+use crate::dialect::Dialect;
+use crate::sql::{CheckedSql, Writer};
+use std::marker::PhantomData;
 
-#[derive(Debug, Clone, Copy)]
-pub struct Bind {
+/// A parameter of type `A` bound in a prepared statement.
+#[derive(Debug)]
+pub struct Bind<A> {
     pub n: u8,
+    _type: PhantomData<A>,
 }
 
-impl CheckedSql for Bind {}
+impl<A> Clone for Bind<A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A> Copy for Bind<A> {}
+
+impl<A> CheckedSql for Bind<A> {}
 
 #[derive(Debug)]
 pub struct Binder {
@@ -19,10 +31,18 @@ impl Default for Binder {
 }
 
 impl Binder {
-    pub fn bind(&mut self) -> Bind {
+    pub fn bind<A>(&mut self) -> Bind<A> {
         let n = self.counter;
         self.counter += 1;
-        Bind { n }
+        Bind {
+            n,
+            _type: PhantomData,
+        }
+    }
+
+    /// The number of parameters bound so far.
+    pub(crate) fn count(&self) -> usize {
+        usize::from(self.counter - 1)
     }
 }
 
@@ -31,7 +51,20 @@ pub trait Binding {
 
     fn bindings(binder: &mut Binder) -> Self::Bindings;
 
-    fn write_types(sql: &mut String);
+    fn write_types<D: Dialect>(sql: &mut Writer<D>);
 
-    fn write_values(&self, sql: &mut String);
+    /// Writes each value after `f` writes what comes before it,
+    /// given the number of its parameter starting from 1.
+    fn write_values_with<D, F>(&self, sql: &mut Writer<D>, f: F)
+    where
+        D: Dialect,
+        F: FnMut(&mut Writer<D>, usize);
+
+    fn write_values<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_values_with(sql, |sql, n| {
+            if n > 1 {
+                sql.push(',');
+            }
+        });
+    }
 }
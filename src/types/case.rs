@@ -0,0 +1,257 @@
+use super::expr::{Expr, TypedExpr};
+use crate::dialect::Dialect;
+use crate::query::select::{Order, Projection, Scalar, WriteQueryable};
+use crate::query::Predicate;
+use crate::{CheckedSql, Writer};
+use std::marker::PhantomData;
+
+/// A conditional expression of type `A`, which is the value of the first branch
+/// whose predicate is true, or the value of `otherwise`.
+///
+/// Every branch must be a value of the type of the first one.
+/// ```
+/// use typed_sql::{Comparable, Query, Table, ToSql};
+/// use typed_sql::query::sum;
+/// use typed_sql::types::case;
+///
+/// #[derive(Table)]
+/// struct Order {
+///     id: i64,
+///     status: String,
+///     amount: i64
+/// }
+///
+/// let stmt = Order::table().select_with(|order| {
+///     (
+///         order.id,
+///         case()
+///             .when(order.amount.gt(100), "large")
+///             .when(order.amount.gt(10), "medium")
+///             .otherwise("small"),
+///     )
+/// });
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "SELECT orders.id,CASE WHEN orders.amount > 100 THEN 'large' \
+///     WHEN orders.amount > 10 THEN 'medium' ELSE 'small' END FROM orders;"
+/// );
+///
+/// let stmt = Order::table()
+///     .aggregate(|order| sum(case().when(order.status.eq("paid"), order.amount).otherwise(0)));
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "SELECT CAST(SUM(CASE WHEN orders.status = 'paid' THEN orders.amount ELSE 0 END) AS BIGINT) \
+///     FROM orders;"
+/// );
+///
+/// let stmt = Order::table().update(|order| {
+///     order
+///         .status
+///         .eq(case().when(order.amount.eq(0), "free").otherwise(order.status))
+/// });
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "UPDATE orders SET status = CASE WHEN orders.amount = 0 THEN 'free' ELSE orders.status END;"
+/// );
+/// ```
+///
+/// ```compile_fail
+/// use typed_sql::{Comparable, Query, Table};
+/// use typed_sql::types::case;
+///
+/// #[derive(Table)]
+/// struct Order {
+///     amount: i64
+/// }
+///
+/// Order::table().select_with(|order| {
+///     case().when(order.amount.gt(100), order.amount).otherwise("small")
+/// });
+/// ```
+pub fn case() -> Case<(), ()> {
+    Case {
+        branches: (),
+        _type: PhantomData,
+    }
+}
+
+/// The branches of a `CASE` expression of type `A`, which is finished by [`Case::otherwise`].
+pub struct Case<A, W> {
+    branches: W,
+    _type: PhantomData<A>,
+}
+
+/// A `WHEN ... THEN ...` branch, following the branches in `prev`.
+pub struct When<P, V, W> {
+    predicate: P,
+    value: V,
+    prev: W,
+}
+
+pub trait Branches<A> {
+    fn write_branches<D: Dialect>(&self, sql: &mut Writer<D>);
+}
+
+impl<P, V, A> Branches<A> for When<P, V, ()>
+where
+    P: Predicate,
+    V: Expr<A>,
+{
+    fn write_branches<D: Dialect>(&self, sql: &mut Writer<D>) {
+        write_when(&self.predicate, &self.value, sql);
+    }
+}
+
+impl<P, V, P2, V2, W2, A> Branches<A> for When<P, V, When<P2, V2, W2>>
+where
+    P: Predicate,
+    V: Expr<A>,
+    When<P2, V2, W2>: Branches<A>,
+{
+    fn write_branches<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.prev.write_branches(sql);
+        write_when(&self.predicate, &self.value, sql);
+    }
+}
+
+fn write_when<P, V, A, D>(predicate: &P, value: &V, sql: &mut Writer<D>)
+where
+    P: Predicate,
+    V: Expr<A>,
+    D: Dialect,
+{
+    sql.push_str(" WHEN ");
+    predicate.write_predicate(sql);
+    sql.push_str(" THEN ");
+    value.write_expr(sql);
+}
+
+impl Case<(), ()> {
+    /// Adds the first branch, whose value decides the type of this expression.
+    pub fn when<P, V>(self, predicate: P, value: V) -> Case<V::Type, When<P, V, ()>>
+    where
+        P: Predicate,
+        V: TypedExpr,
+    {
+        Case {
+            branches: When {
+                predicate,
+                value,
+                prev: (),
+            },
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<A, W: Branches<A>> Case<A, W> {
+    pub fn when<P, V>(self, predicate: P, value: V) -> Case<A, When<P, V, W>>
+    where
+        P: Predicate,
+        V: Expr<A>,
+    {
+        Case {
+            branches: When {
+                predicate,
+                value,
+                prev: self.branches,
+            },
+            _type: PhantomData,
+        }
+    }
+
+    /// Sets the value when no branch matches, finishing the expression.
+    pub fn otherwise<V: Expr<A>>(self, value: V) -> CaseExpr<A, W, V> {
+        CaseExpr {
+            branches: self.branches,
+            otherwise: value,
+            _type: PhantomData,
+        }
+    }
+}
+
+/// A finished `CASE` expression of type `A`.
+pub struct CaseExpr<A, W, E> {
+    branches: W,
+    otherwise: E,
+    _type: PhantomData<A>,
+}
+
+impl<A, W, E> CaseExpr<A, W, E>
+where
+    W: Branches<A>,
+    E: Expr<A>,
+{
+    fn write_case<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("CASE");
+        self.branches.write_branches(sql);
+        sql.push_str(" ELSE ");
+        self.otherwise.write_expr(sql);
+        sql.push_str(" END");
+    }
+}
+
+impl<A, W, E> Expr<A> for CaseExpr<A, W, E>
+where
+    W: Branches<A>,
+    E: Expr<A>,
+{
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_case(sql);
+    }
+}
+
+impl<A, W, E> TypedExpr for CaseExpr<A, W, E>
+where
+    W: Branches<A>,
+    E: Expr<A>,
+{
+    type Type = A;
+}
+
+impl<A, W, E> WriteQueryable for CaseExpr<A, W, E>
+where
+    W: Branches<A>,
+    E: Expr<A>,
+{
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_case(sql);
+    }
+}
+
+impl<A, W, E> Projection for CaseExpr<A, W, E>
+where
+    W: Branches<A>,
+    E: Expr<A>,
+{
+    type Output = A;
+}
+
+impl<A, W, E> Scalar for CaseExpr<A, W, E>
+where
+    W: Branches<A>,
+    E: Expr<A>,
+{
+}
+
+/// Rows are ordered by the value of the expression.
+impl<A, W, E> Order for CaseExpr<A, W, E>
+where
+    W: Branches<A>,
+    E: Expr<A>,
+{
+    fn write_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_case(sql);
+    }
+
+    fn write_output_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_case(sql);
+    }
+}
+
+impl<A, W: CheckedSql, E: CheckedSql> CheckedSql for CaseExpr<A, W, E> {}
+
+impl<P: CheckedSql, V: CheckedSql, W: CheckedSql> CheckedSql for When<P, V, W> {}
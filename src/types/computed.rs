@@ -0,0 +1,553 @@
+//! Expressions computed from columns and values.
+//!
+//! Arithmetic is written with the operators `+ - * /` on numeric columns,
+//! and functions such as [`lower`] accept any [`TypedExpr`], so they can be nested.
+//! ```
+//! use typed_sql::{Comparable, Query, Table, ToSql};
+//! use typed_sql::types::computed::{coalesce, concat, lower};
+//!
+//! #[derive(Table)]
+//! struct User {
+//!     id: i64,
+//!     first_name: String,
+//!     last_name: String,
+//!     nickname: Option<String>,
+//!     score: i64,
+//!     bonus: i64
+//! }
+//!
+//! let stmt = User::table()
+//!     .select_with(|user| (concat(user.first_name, user.last_name), user.score + user.bonus * 2))
+//!     .filter(|user| lower(coalesce(user.nickname, user.first_name)).eq("bob"))
+//!     .order_by(|user| (user.score + user.bonus).descending());
+//!
+//! assert_eq!(
+//!     stmt.to_sql(),
+//!     "SELECT CONCAT(users.first_name, users.last_name),users.score + (users.bonus * 2) \
+//!     FROM users WHERE LOWER(COALESCE(users.nickname, users.first_name)) = 'bob' \
+//!     ORDER BY users.score + users.bonus DESC;"
+//! );
+//! ```
+//!
+//! Computed values can also be assigned and grouped by.
+//! ```
+//! use typed_sql::{Comparable, Query, Table, ToSql};
+//! use typed_sql::types::computed::{length, now, upper};
+//! use std::time::SystemTime;
+//!
+//! #[derive(Table)]
+//! struct Post {
+//!     id: i64,
+//!     title: String,
+//!     views: i64,
+//!     edited_at: SystemTime,
+//!     published_at: Option<SystemTime>
+//! }
+//!
+//! let stmt = Post::table().update(|post| {
+//!     post.views
+//!         .eq(post.views + 1)
+//!         .and(post.title.eq(upper(post.title)))
+//!         .and(post.edited_at.eq(now()))
+//!         .and(post.published_at.eq(now()))
+//! });
+//!
+//! assert_eq!(
+//!     stmt.to_sql(),
+//!     "UPDATE posts SET views = posts.views + 1,title = UPPER(posts.title),\
+//!     edited_at = CURRENT_TIMESTAMP,published_at = CURRENT_TIMESTAMP;"
+//! );
+//!
+//! let stmt = Post::table()
+//!     .select_with(|post| (length(post.title), post.id.cast::<String>()))
+//!     .group_by(|post| length(post.title));
+//!
+//! assert_eq!(
+//!     stmt.to_sql(),
+//!     "SELECT LENGTH(posts.title),CAST(posts.id AS TEXT) FROM posts \
+//!     GROUP BY LENGTH(posts.title);"
+//! );
+//! ```
+
+use super::{Expr, Field, NotNull, Nullable, TypedExpr};
+use crate::dialect::Dialect;
+use crate::query::predicate::Comparable;
+use crate::query::select::order::{Ascending, Descending, Direction};
+use crate::query::select::{GroupOrder, Order, Projection, Scalar, WriteQueryable};
+use crate::{CheckedSql, Table, Writer};
+use std::marker::PhantomData;
+use std::ops;
+use std::time::SystemTime;
+
+/// The parts of a computed expression.
+pub trait Compute {
+    /// Whether this is an arithmetic operation, which is grouped when it's an operand.
+    const IS_OPERATION: bool = false;
+
+    fn write_computed<D: Dialect>(&self, sql: &mut Writer<D>);
+}
+
+/// An expression of type `A` computed from columns and values.
+pub struct Computed<E, A> {
+    inner: E,
+    _type: PhantomData<A>,
+}
+
+impl<E, A> Computed<E, A> {
+    pub(crate) fn new(inner: E) -> Self {
+        Self {
+            inner,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<E: Compute, A> Computed<E, A> {
+    pub fn cast<B>(self) -> Computed<Cast<Self, A, B>, A::Output>
+    where
+        A: CastTo<B>,
+        B: Castable,
+    {
+        Computed::new(Cast::new(self))
+    }
+
+    pub fn ascending(self) -> OrderedExpr<Self, A, Ascending> {
+        OrderedExpr::new(self)
+    }
+
+    pub fn descending(self) -> OrderedExpr<Self, A, Descending> {
+        OrderedExpr::new(self)
+    }
+}
+
+impl<E: Compute, A> Expr<A> for Computed<E, A> {
+    const IS_OPERATION: bool = E::IS_OPERATION;
+
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.inner.write_computed(sql);
+    }
+}
+
+/// Computed values can be compared with and assigned to nullable columns, like other values.
+impl<E: Compute, A> Expr<Option<A>> for Computed<E, A> {
+    const IS_OPERATION: bool = E::IS_OPERATION;
+
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.inner.write_computed(sql);
+    }
+}
+
+impl<E: Compute, A> TypedExpr for Computed<E, A> {
+    type Type = A;
+}
+
+impl<E: Compute, A> WriteQueryable for Computed<E, A> {
+    fn write_query<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.inner.write_computed(sql);
+    }
+}
+
+impl<E: Compute, A> Projection for Computed<E, A> {
+    type Output = A;
+}
+
+impl<E: Compute, A> Scalar for Computed<E, A> {}
+
+impl<E: Compute, A> GroupOrder for Computed<E, A> {
+    fn write_columns<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.inner.write_computed(sql);
+    }
+}
+
+impl<E: Compute, A> Order for Computed<E, A> {
+    fn write_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.inner.write_computed(sql);
+    }
+
+    fn write_output_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.inner.write_computed(sql);
+    }
+}
+
+impl<E: Compute, A> Comparable<A> for Computed<E, A> {}
+
+impl<E: CheckedSql, A> CheckedSql for Computed<E, A> {}
+
+/// An expression ordered in the direction `Dir`.
+pub struct OrderedExpr<E, A, Dir> {
+    expr: E,
+    _type: PhantomData<A>,
+    _direction: PhantomData<Dir>,
+}
+
+impl<E, A, Dir> OrderedExpr<E, A, Dir> {
+    fn new(expr: E) -> Self {
+        Self {
+            expr,
+            _type: PhantomData,
+            _direction: PhantomData,
+        }
+    }
+}
+
+impl<E: Expr<A>, A, Dir: Direction> Order for OrderedExpr<E, A, Dir> {
+    fn write_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.expr.write_expr(sql);
+        sql.push(' ');
+        sql.push_str(Dir::DIRECTION);
+    }
+
+    fn write_output_order<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_order(sql);
+    }
+}
+
+/// A type that can be added, subtracted, multiplied and divided.
+pub trait Numeric {}
+
+macro_rules! impl_numeric {
+    ($($ty:ty),*) => {
+        $(
+            impl Numeric for $ty {}
+        )*
+    };
+}
+
+impl_numeric!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+impl<N: Numeric> Numeric for Option<N> {}
+
+/// An arithmetic operation on values of type `A`.
+pub struct Binary<L, R, A> {
+    operator: &'static str,
+    lhs: L,
+    rhs: R,
+    _type: PhantomData<A>,
+}
+
+impl<L, R, A> Compute for Binary<L, R, A>
+where
+    L: Expr<A>,
+    R: Expr<A>,
+{
+    const IS_OPERATION: bool = true;
+
+    fn write_computed<D: Dialect>(&self, sql: &mut Writer<D>) {
+        write_operand(&self.lhs, sql);
+        sql.push(' ');
+        sql.push_str(self.operator);
+        sql.push(' ');
+        write_operand(&self.rhs, sql);
+    }
+}
+
+impl<L: CheckedSql, R: CheckedSql, A> CheckedSql for Binary<L, R, A> {}
+
+fn write_operand<E: Expr<A>, A, D: Dialect>(expr: &E, sql: &mut Writer<D>) {
+    if E::IS_OPERATION {
+        sql.push('(');
+        expr.write_expr(sql);
+        sql.push(')');
+    } else {
+        expr.write_expr(sql);
+    }
+}
+
+macro_rules! impl_arithmetic {
+    ($($trait:ident::$method:ident($operator:literal)),*) => {
+        $(
+            impl<T: Table, A: Numeric, R: Expr<A>> ops::$trait<R> for Field<T, A> {
+                type Output = Computed<Binary<Self, R, A>, A>;
+
+                fn $method(self, rhs: R) -> Self::Output {
+                    Computed::new(Binary {
+                        operator: $operator,
+                        lhs: self,
+                        rhs,
+                        _type: PhantomData,
+                    })
+                }
+            }
+
+            impl<E: Compute, A: Numeric, R: Expr<A>> ops::$trait<R> for Computed<E, A> {
+                type Output = Computed<Binary<Self, R, A>, A>;
+
+                fn $method(self, rhs: R) -> Self::Output {
+                    Computed::new(Binary {
+                        operator: $operator,
+                        lhs: self,
+                        rhs,
+                        _type: PhantomData,
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_arithmetic!(Add::add("+"), Sub::sub("-"), Mul::mul("*"), Div::div("/"));
+
+/// A string type, or a nullable one.
+pub trait Text {
+    /// The type of the length of this string.
+    type Length;
+}
+
+impl Text for String {
+    type Length = i32;
+}
+
+impl Text for Option<String> {
+    type Length = Option<i32>;
+}
+
+/// A boolean type, or a nullable one.
+pub trait Boolean {}
+
+impl Boolean for bool {}
+
+impl Boolean for Option<bool> {}
+
+/// A function of one argument of type `A`.
+pub struct Function<E, A> {
+    name: &'static str,
+    arg: E,
+    _type: PhantomData<A>,
+}
+
+impl<E: Expr<A>, A> Compute for Function<E, A> {
+    fn write_computed<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str(self.name);
+        sql.push('(');
+        self.arg.write_expr(sql);
+        sql.push(')');
+    }
+}
+
+impl<E: CheckedSql, A> CheckedSql for Function<E, A> {}
+
+fn function<E, A, B>(name: &'static str, arg: E) -> Computed<Function<E, A>, B> {
+    Computed::new(Function {
+        name,
+        arg,
+        _type: PhantomData,
+    })
+}
+
+pub fn lower<E>(expr: E) -> Computed<Function<E, E::Type>, E::Type>
+where
+    E: TypedExpr,
+    E::Type: Text,
+{
+    function("LOWER", expr)
+}
+
+pub fn upper<E>(expr: E) -> Computed<Function<E, E::Type>, E::Type>
+where
+    E: TypedExpr,
+    E::Type: Text,
+{
+    function("UPPER", expr)
+}
+
+/// The number of characters in a string, written as `CHAR_LENGTH` in MySQL and `LEN` in SQL Server.
+pub fn length<E>(expr: E) -> Computed<Length<E, E::Type>, <E::Type as Text>::Length>
+where
+    E: TypedExpr,
+    E::Type: Text,
+{
+    Computed::new(Length {
+        arg: expr,
+        _type: PhantomData,
+    })
+}
+
+pub struct Length<E, A> {
+    arg: E,
+    _type: PhantomData<A>,
+}
+
+impl<E: Expr<A>, A> Compute for Length<E, A> {
+    fn write_computed<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str(D::LENGTH);
+        sql.push('(');
+        self.arg.write_expr(sql);
+        sql.push(')');
+    }
+}
+
+impl<E: CheckedSql, A> CheckedSql for Length<E, A> {}
+
+/// Joins two strings, written with `||` in SQLite.
+pub fn concat<L, R>(lhs: L, rhs: R) -> Computed<Concat<L, R, L::Type>, L::Type>
+where
+    L: TypedExpr,
+    L::Type: Text,
+    R: Expr<L::Type>,
+{
+    Computed::new(Concat {
+        lhs,
+        rhs,
+        _type: PhantomData,
+    })
+}
+
+pub struct Concat<L, R, A> {
+    lhs: L,
+    rhs: R,
+    _type: PhantomData<A>,
+}
+
+impl<L: Expr<A>, R: Expr<A>, A> Compute for Concat<L, R, A> {
+    fn write_computed<D: Dialect>(&self, sql: &mut Writer<D>) {
+        D::write_concat(sql, &self.lhs, &self.rhs);
+    }
+}
+
+impl<L: CheckedSql, R: CheckedSql, A> CheckedSql for Concat<L, R, A> {}
+
+/// The value of a nullable expression `A`, which is `Inner` when it's not `NULL`.
+pub trait Optional {
+    type Inner;
+}
+
+impl<A> Optional for Option<A> {
+    type Inner = A;
+}
+
+/// The value of `expr`, or `default` when it's `NULL`.
+pub fn coalesce<E, V>(expr: E, default: V) -> Computed<Coalesce<E, V, E::Type>, Inner<E>>
+where
+    E: TypedExpr,
+    E::Type: Optional,
+    V: Expr<Inner<E>>,
+{
+    Computed::new(Coalesce {
+        expr,
+        default,
+        _type: PhantomData,
+    })
+}
+
+type Inner<E> = <<E as TypedExpr>::Type as Optional>::Inner;
+
+pub struct Coalesce<E, V, A> {
+    expr: E,
+    default: V,
+    _type: PhantomData<A>,
+}
+
+impl<E, V, A> Compute for Coalesce<E, V, A>
+where
+    E: Expr<A>,
+    A: Optional,
+    V: Expr<A::Inner>,
+{
+    fn write_computed<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("COALESCE(");
+        self.expr.write_expr(sql);
+        sql.push_str(", ");
+        self.default.write_expr(sql);
+        sql.push(')');
+    }
+}
+
+impl<E: CheckedSql, V: CheckedSql, A> CheckedSql for Coalesce<E, V, A> {}
+
+/// The types an expression can be cast to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CastType {
+    SmallInt,
+    Integer,
+    BigInt,
+    Real,
+    Double,
+    Text,
+    Boolean,
+    Bytes,
+}
+
+/// A type an expression can be cast to, written for each dialect by [`Dialect::write_cast_type`].
+pub trait Castable {
+    const CAST_TYPE: CastType;
+}
+
+macro_rules! impl_castable {
+    ($($ty:ty => $cast:ident),*) => {
+        $(
+            impl Castable for $ty {
+                const CAST_TYPE: CastType = CastType::$cast;
+            }
+        )*
+    };
+}
+
+impl_castable!(
+    i16 => SmallInt,
+    i32 => Integer,
+    i64 => BigInt,
+    f32 => Real,
+    f64 => Double,
+    String => Text,
+    bool => Boolean,
+    Vec<u8> => Bytes
+);
+
+impl<C: Castable> Castable for Option<C> {
+    const CAST_TYPE: CastType = C::CAST_TYPE;
+}
+
+/// The type of a value of type `Self` cast to `B`, which is nullable when `Self` is.
+pub trait CastTo<B> {
+    type Output;
+}
+
+impl<A: NotNull, B> CastTo<B> for A {
+    type Output = B;
+}
+
+impl<A: NotNull, B: Nullable> CastTo<B> for Option<A> {
+    type Output = B::Output;
+}
+
+/// An expression of type `A` converted to the type `B`.
+pub struct Cast<E, A, B> {
+    expr: E,
+    _type: PhantomData<(A, B)>,
+}
+
+impl<E, A, B> Cast<E, A, B> {
+    pub(crate) fn new(expr: E) -> Self {
+        Self {
+            expr,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<E: Expr<A>, A, B: Castable> Compute for Cast<E, A, B> {
+    fn write_computed<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("CAST(");
+        self.expr.write_expr(sql);
+        sql.push_str(" AS ");
+        D::write_cast_type(sql, B::CAST_TYPE);
+        sql.push(')');
+    }
+}
+
+impl<E: CheckedSql, A, B> CheckedSql for Cast<E, A, B> {}
+
+/// The current date and time.
+pub fn now() -> Computed<Now, SystemTime> {
+    Computed::new(Now)
+}
+
+pub struct Now;
+
+impl Compute for Now {
+    fn write_computed<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.push_str("CURRENT_TIMESTAMP");
+    }
+}
+
+impl CheckedSql for Now {}
@@ -0,0 +1,236 @@
+use super::{Bind, Field, NotNull, Primitive};
+use crate::dialect::Dialect;
+use crate::{Table, Writer};
+use std::borrow::Cow;
+
+/// An expression that can be used where a value of type `A` is expected.
+///
+/// Values are accepted for their own type and for nullable columns of that type,
+/// and columns can be compared with columns of the same or a wider type.
+/// ```compile_fail
+/// use typed_sql::{Comparable, Query, Table, ToSql};
+///
+/// #[derive(Table)]
+/// struct User {
+///     id: i64
+/// }
+///
+/// User::table().select().filter(|user| user.id.eq("abc"));
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be used as a value of type `{A}`",
+    label = "expected a value, column or bind of type `{A}`"
+)]
+pub trait Expr<A> {
+    /// Whether this expression is an arithmetic operation, which is grouped when it's an operand.
+    const IS_OPERATION: bool = false;
+
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>);
+
+    /// Whether this expression is the `NULL` literal.
+    fn is_null(&self) -> bool {
+        false
+    }
+}
+
+/// The right side of a comparison with a value of type `A`, which is compared as `Self::Type`.
+///
+/// This is any expression of type `A`, or a nullable column of `A` that's compared as an `Option<A>`,
+/// where `M` tells the two apart.
+/// A nullable column can't otherwise stand in for a value, such as in assignments or arithmetic.
+/// ```
+/// use typed_sql::{Comparable, Query, Table, ToSql};
+///
+/// #[derive(Table)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>
+/// }
+///
+/// let stmt = User::table().select().filter(|user| user.name.eq(user.nickname));
+///
+/// assert_eq!(stmt.to_sql(), "SELECT * FROM users WHERE users.name = users.nickname;");
+/// ```
+///
+/// ```compile_fail
+/// use typed_sql::{Comparable, Query, Table};
+///
+/// #[derive(Table)]
+/// struct User {
+///     name: String,
+///     nickname: Option<String>
+/// }
+///
+/// User::table().update(|user| user.name.eq(user.nickname));
+/// ```
+///
+/// ```compile_fail
+/// use typed_sql::{Query, Table};
+///
+/// #[derive(Table)]
+/// struct User {
+///     score: i64,
+///     bonus: Option<i64>
+/// }
+///
+/// User::table().select_with(|user| user.score + user.bonus);
+/// ```
+pub trait Operand<A, M> {
+    type Type;
+}
+
+/// Marks an [`Operand`] that's an expression of the compared type.
+pub struct SameType;
+
+/// Marks an [`Operand`] that's a nullable column of the compared type.
+pub struct NullableColumn;
+
+impl<A, E: Expr<A>> Operand<A, SameType> for E {
+    type Type = A;
+}
+
+impl<T: Table, A> Operand<A, NullableColumn> for Field<T, Option<A>> {
+    type Type = Option<A>;
+}
+
+/// An expression with a type of its own, such as a column, a value or a function of them.
+pub trait TypedExpr: Expr<<Self as TypedExpr>::Type> {
+    type Type;
+}
+
+impl<P: Primitive> Expr<P> for P {
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_value(self);
+    }
+
+    fn is_null(&self) -> bool {
+        Primitive::is_null(self)
+    }
+}
+
+impl<A, P> Expr<Option<A>> for P
+where
+    P: NotNull + Expr<A>,
+{
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_value(self);
+    }
+}
+
+macro_rules! impl_typed_expr {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl TypedExpr for $ty {
+                type Type = $ty;
+            }
+        )*
+    };
+}
+
+impl_typed_expr!(
+    String,
+    char,
+    i8,
+    i16,
+    i32,
+    i64,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    usize,
+    f32,
+    f64,
+    bool,
+    Vec<u8>,
+);
+
+/// Borrowed values have the type of their owned column.
+impl TypedExpr for &'_ str {
+    type Type = String;
+}
+
+impl TypedExpr for Cow<'_, str> {
+    type Type = String;
+}
+
+impl TypedExpr for &'_ [u8] {
+    type Type = Vec<u8>;
+}
+
+impl<P: NotNull + TypedExpr<Type = P>> TypedExpr for Option<P> {
+    type Type = Option<P>;
+}
+
+impl Expr<String> for &'_ str {
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_value(self);
+    }
+}
+
+impl Expr<String> for Cow<'_, str> {
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_value(self);
+    }
+}
+
+impl Expr<Vec<u8>> for &'_ [u8] {
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_value(self);
+    }
+}
+
+impl<T: Table, A> Expr<A> for Field<T, A> {
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_field(sql);
+    }
+}
+
+impl<T: Table, A> TypedExpr for Field<T, A> {
+    type Type = A;
+}
+
+impl<T: Table, A> Expr<Option<A>> for Field<T, A> {
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        self.write_field(sql);
+    }
+}
+
+macro_rules! impl_widening {
+    ($($from:ty => $($to:ty),*;)*) => {
+        $($(
+            impl<T: Table> Expr<$to> for Field<T, $from> {
+                fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+                    self.write_field(sql);
+                }
+            }
+        )*)*
+    };
+}
+
+impl_widening! {
+    i8 => i16, i32, i64;
+    i16 => i32, i64;
+    i32 => i64;
+    u8 => i16, i32, i64, u16, u32, u64;
+    u16 => i32, i64, u32, u64;
+    u32 => i64, u64;
+    f32 => f64;
+}
+
+impl<A> Expr<A> for Bind<A> {
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_placeholder(self.n.into());
+    }
+}
+
+impl<A> Expr<Option<A>> for Bind<A> {
+    fn write_expr<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_placeholder(self.n.into());
+    }
+}
+
+impl<A> TypedExpr for Bind<A> {
+    type Type = A;
+}
@@ -1,6 +1,11 @@
-use crate::query::predicate::{Eq, Gt, Lt, Neq, Op};
+use crate::dialect::Dialect;
+use crate::query::predicate::{Comparable, In, InSelect, NotIn};
 use crate::query::select::order::{Ascending, Descending, Ordered};
-use crate::Table;
+use crate::query::select::queryable::Distinct;
+use crate::query::select::{Projection, Scalar, Select, Subquery};
+use crate::types::computed::{Cast, CastTo, Castable, Computed};
+use crate::types::Expr;
+use crate::{CheckedSql, Table, Writer};
 
 use std::marker::PhantomData;
 
@@ -10,10 +15,7 @@ pub struct Field<T, A> {
     _type: PhantomData<A>,
 }
 
-impl<T, A> Field<T, A>
-where
-    T: Table,
-{
+impl<T, A> Field<T, A> {
     pub fn new(name: &'static str) -> Self {
         Self {
             name,
@@ -21,21 +23,51 @@ where
             _type: PhantomData,
         }
     }
+}
 
-    pub fn eq<U>(self, rhs: U) -> Op<T, A, U, Eq> {
-        Op::new(self, rhs)
-    }
-
-    pub fn neq<U>(self, rhs: U) -> Op<T, A, U, Neq> {
-        Op::new(self, rhs)
-    }
-
-    pub fn gt<U>(self, rhs: U) -> Op<T, A, U, Gt> {
-        Op::new(self, rhs)
+impl<T, A> Field<T, A>
+where
+    T: Table,
+{
+    /// ```
+    /// use typed_sql::{Query, Table, ToSql};
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String
+    /// }
+    ///
+    /// #[derive(Table)]
+    /// struct Ban {
+    ///     user_id: i64
+    /// }
+    ///
+    /// let stmt = User::table()
+    ///     .select()
+    ///     .filter(|user| user.id.not_in_select(Ban::table().select_with(|ban| ban.user_id)));
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT * FROM users WHERE users.id NOT IN (SELECT bans.user_id FROM bans);"
+    /// );
+    /// ```
+    pub fn in_select<S>(self, select: S) -> InSelect<T, A, S, In>
+    where
+        S: Select,
+        S::Queryable: Scalar,
+        Subquery<S, <S::Queryable as Projection>::Output>: Expr<A>,
+    {
+        InSelect::new(self, select)
     }
 
-    pub fn lt<U>(self, rhs: U) -> Op<T, A, U, Lt> {
-        Op::new(self, rhs)
+    pub fn not_in_select<S>(self, select: S) -> InSelect<T, A, S, NotIn>
+    where
+        S: Select,
+        S::Queryable: Scalar,
+        Subquery<S, <S::Queryable as Projection>::Output>: Expr<A>,
+    {
+        InSelect::new(self, select)
     }
 
     pub fn then<T2>(self, next: T2) -> Then<Self, T2> {
@@ -45,6 +77,42 @@ where
         }
     }
 
+    /// Counts only the distinct values of this column.
+    pub fn distinct(self) -> Distinct<Self> {
+        Distinct::new(self)
+    }
+
+    /// Converts this column to the type `B`, which is nullable when the column is.
+    /// ```
+    /// use typed_sql::{Query, Table, ToSql};
+    /// use typed_sql::types::computed::{Cast, Computed};
+    /// use typed_sql::types::Field;
+    ///
+    /// #[derive(Table)]
+    /// struct User {
+    ///     id: i64,
+    ///     nickname: Option<String>
+    /// }
+    ///
+    /// let stmt = User::table().select_with(|user| {
+    ///     let id: Computed<Cast<Field<User, i64>, i64, String>, String> = user.id.cast();
+    ///     let nickname: Computed<_, Option<i32>> = user.nickname.cast::<i32>();
+    ///     (id, nickname)
+    /// });
+    ///
+    /// assert_eq!(
+    ///     stmt.to_sql(),
+    ///     "SELECT CAST(users.id AS TEXT),CAST(users.nickname AS INTEGER) FROM users;"
+    /// );
+    /// ```
+    pub fn cast<B>(self) -> Computed<Cast<Self, A, B>, A::Output>
+    where
+        A: CastTo<B>,
+        B: Castable,
+    {
+        Computed::new(Cast::new(self))
+    }
+
     pub fn ascending(self) -> Ordered<T, A, Ascending> {
         Ordered::new(self)
     }
@@ -53,15 +121,22 @@ where
         Ordered::new(self)
     }
 
-    pub(crate) fn write_field(&self, sql: &mut String) {
-        sql.push_str(T::NAME);
-        sql.push('.');
-        sql.push_str(self.name);
+    pub(crate) fn write_field<D: Dialect>(&self, sql: &mut Writer<D>) {
+        T::write_qualified(sql, self.name);
+    }
+
+    pub(crate) fn write_column<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_ident(self.name);
     }
 }
 
+impl<T: Table, A> Comparable<A> for Field<T, A> {}
+
 impl<T, A> Copy for Field<T, A> {}
 
+/// Column names are written by the table derive, never from input.
+impl<T, A> CheckedSql for Field<T, A> {}
+
 impl<T, A> Clone for Field<T, A> {
     fn clone(&self) -> Self {
         *self
@@ -1,43 +1,232 @@
+use crate::dialect::Dialect;
+use crate::Writer;
+use std::borrow::Cow;
 use std::fmt::Write;
 
 pub mod bind;
 pub use bind::{Bind, Binding};
 
+pub mod case;
+pub use case::case;
+
+pub mod computed;
+
+pub mod expr;
+pub use expr::{Expr, Operand, TypedExpr};
+
 pub mod field;
 pub use field::Field;
 
+mod value;
+pub use value::Value;
+
+/// A value that can be written as a SQL literal or bound as a parameter.
+///
+/// ```
+/// use typed_sql::{Insertable, Query, Table, ToSql};
+///
+/// #[derive(Table, Insertable)]
+/// struct Reading {
+///     sensor: u16,
+///     value: f64,
+///     valid: bool,
+///     raw: Vec<u8>
+/// }
+///
+/// let stmt = Reading::table().insert(Reading {
+///     sensor: 7,
+///     value: f64::NAN,
+///     valid: false,
+///     raw: vec![0xde, 0xad],
+/// });
+///
+/// assert_eq!(
+///     stmt.to_sql(),
+///     "INSERT INTO readings(sensor,value,valid,raw) \
+///     VALUES (7,'NaN',FALSE,decode('dead', 'hex'));"
+/// );
+/// ```
 pub trait Primitive {
-    fn write_primative(&self, sql: &mut String);
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>);
+
+    fn to_value(&self) -> Value;
+
+    fn is_null(&self) -> bool {
+        false
+    }
+}
+
+/// A primitive that is never `NULL`, so it can also be compared with nullable columns.
+pub trait NotNull: Primitive {}
+
+/// The nullable version of a type, such as the result of an aggregate over no rows.
+pub trait Nullable {
+    type Output;
+}
+
+impl<P: NotNull> Nullable for P {
+    type Output = Option<P>;
+}
+
+impl<P: NotNull> Nullable for Option<P> {
+    type Output = Option<P>;
 }
 
 impl Primitive for String {
-    fn write_primative(&self, sql: &mut String) {
-        sql.push('\'');
-        sql.push_str(&self);
-        sql.push('\'');
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_str(self);
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Text(self.clone())
     }
 }
 
+impl NotNull for String {}
+
 impl Primitive for &'_ str {
-    fn write_primative(&self, sql: &mut String) {
-        sql.push('\'');
-        sql.push_str(self);
-        sql.push('\'');
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_str(self);
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Text(self.to_string())
+    }
+}
+
+impl NotNull for &'_ str {}
+
+impl Primitive for Cow<'_, str> {
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_str(self);
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Text(self.to_string())
+    }
+}
+
+impl NotNull for Cow<'_, str> {}
+
+impl Primitive for char {
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
+        sql.write_str(self.encode_utf8(&mut [0; 4]));
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Text(self.to_string())
+    }
+}
+
+impl NotNull for char {}
+
+macro_rules! impl_primitive_int {
+    ($variant:ident($as:ty) for $($ty:ty),*) => {
+        $(
+            impl Primitive for $ty {
+                fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
+                    sql.write_fmt(format_args!("{}", self)).unwrap();
+                }
+
+                fn to_value(&self) -> Value {
+                    Value::$variant(*self as $as)
+                }
+            }
+
+            impl NotNull for $ty {}
+        )*
+    };
+}
+
+impl_primitive_int!(Int(i64) for i8, i16, i32, i64, isize, u8, u16, u32);
+
+impl_primitive_int!(UInt(u64) for u64, usize);
+
+impl Primitive for f32 {
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
+        if self.is_finite() {
+            sql.write_fmt(format_args!("{:?}", self)).unwrap();
+        } else {
+            D::write_non_finite(sql, f64::from(*self));
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Float(f64::from(*self))
     }
 }
 
-impl Primitive for i64 {
-    fn write_primative(&self, sql: &mut String) {
-        sql.write_fmt(format_args!("{}", self)).unwrap();
+impl NotNull for f32 {}
+
+impl Primitive for f64 {
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
+        if self.is_finite() {
+            sql.write_fmt(format_args!("{:?}", self)).unwrap();
+        } else {
+            D::write_non_finite(sql, *self);
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Float(*self)
+    }
+}
+
+impl NotNull for f64 {}
+
+impl Primitive for bool {
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
+        D::write_bool(sql, *self);
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
     }
 }
 
+impl NotNull for bool {}
+
+impl Primitive for Vec<u8> {
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
+        D::write_bytes(sql, self);
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Bytes(self.clone())
+    }
+}
+
+impl NotNull for Vec<u8> {}
+
+impl Primitive for &'_ [u8] {
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
+        D::write_bytes(sql, self);
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Bytes(self.to_vec())
+    }
+}
+
+impl NotNull for &'_ [u8] {}
+
 impl<P: Primitive> Primitive for Option<P> {
-    fn write_primative(&self, sql: &mut String) {
+    fn write_primative<D: Dialect>(&self, sql: &mut Writer<D>) {
         if let Some(primative) = self {
             primative.write_primative(sql);
         } else {
             sql.push_str("NULL");
         }
     }
+
+    fn to_value(&self) -> Value {
+        self.as_ref().map_or(Value::Null, Primitive::to_value)
+    }
+
+    fn is_null(&self) -> bool {
+        match self {
+            Some(primative) => primative.is_null(),
+            None => true,
+        }
+    }
 }
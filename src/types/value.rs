@@ -0,0 +1,13 @@
+/// A value bound as a statement parameter.
+///
+/// See [`ToSql::to_parameterized`](crate::ToSql::to_parameterized).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+}
@@ -1,8 +1,9 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use proc_macro2::Span;
-use quote::{format_ident, quote};
-use syn::{parse_macro_input, Data, DataStruct, DeriveInput, Fields, Ident};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DataStruct, DeriveInput, Field, Fields, Ident, Type};
 
 #[proc_macro_derive(Table)]
 pub fn table(input: TokenStream) -> TokenStream {
@@ -24,7 +25,7 @@ pub fn table(input: TokenStream) -> TokenStream {
             let name = &field.ident;
             let ty = &field.ty;
             quote! {
-                #name: typed_sql::types::Field::<#ident, #ty>,
+                #name: typed_sql::types::Field::<T, #ty>,
             }
         });
 
@@ -34,6 +35,55 @@ pub fn table(input: TokenStream) -> TokenStream {
                 #name: typed_sql::types::Field::new(stringify!(#name)),
             }
         });
+        let nullable_default_fields = default_fields.clone();
+
+        let write_columns = fields.named.iter().enumerate().map(|(i, field)| {
+            let name = &field.ident;
+            let separator = if i > 0 {
+                quote! { sql.push(','); }
+            } else {
+                quote! {}
+            };
+            quote! {
+                #separator
+                typed_sql::query::select::WriteQueryable::write_query(&self.#name, sql);
+            }
+        });
+
+        let nullable_fields_ident = format_ident!("{}NullableFields", ident);
+        let nullable_struct_fields = fields.named.iter().map(|field| {
+            let name = &field.ident;
+            let ty = &field.ty;
+            if is_option(ty) {
+                quote! {
+                    #name: typed_sql::types::Field::<T, #ty>,
+                }
+            } else {
+                quote! {
+                    #name: typed_sql::types::Field::<T, Option<#ty>>,
+                }
+            }
+        });
+
+        let column_types = fields.named.iter().map(|field| &field.ty);
+        let column_types = if fields.named.len() == 1 {
+            quote! { #(#column_types)* }
+        } else {
+            quote! { (#(#column_types,)*) }
+        };
+
+        let write_column_names = fields.named.iter().enumerate().map(|(i, field)| {
+            let name = &field.ident;
+            let separator = if i > 0 {
+                quote! { sql.push(','); }
+            } else {
+                quote! {}
+            };
+            quote! {
+                #separator
+                sql.write_ident(stringify!(#name));
+            }
+        });
 
         let table_name = {
             let mut s = ident.to_string().to_lowercase();
@@ -42,22 +92,64 @@ pub fn table(input: TokenStream) -> TokenStream {
         };
 
         let expanded = quote! {
-            struct #fields_ident {
+            struct #fields_ident<T = #ident> {
               #(#struct_fields)*
+              __table: std::marker::PhantomData<T>,
             }
 
-            impl Default for #fields_ident {
+            impl<T> Default for #fields_ident<T> {
                 fn default() -> Self {
                     Self {
                         #(#default_fields)*
+                        __table: std::marker::PhantomData,
                     }
                 }
             }
 
+            struct #nullable_fields_ident<T = #ident> {
+              #(#nullable_struct_fields)*
+              __table: std::marker::PhantomData<T>,
+            }
+
+            impl<T> Default for #nullable_fields_ident<T> {
+                fn default() -> Self {
+                    Self {
+                        #(#nullable_default_fields)*
+                        __table: std::marker::PhantomData,
+                    }
+                }
+            }
+
+            impl<T: typed_sql::Table> typed_sql::query::select::WriteQueryable for #fields_ident<T> {
+                fn write_query<D: typed_sql::dialect::Dialect>(&self, sql: &mut typed_sql::Writer<D>) {
+                    #(#write_columns)*
+                }
+            }
+
+            impl<T: typed_sql::Table> typed_sql::query::select::Projection for #fields_ident<T> {
+                type Output = #ident;
+            }
+
             impl typed_sql::Table for #ident {
                 const NAME: &'static str = stringify!(#table_name);
 
                 type Fields = #fields_ident;
+
+                type NullableFields = #nullable_fields_ident;
+            }
+
+            impl typed_sql::table::Columns for #ident {
+                type Types = #column_types;
+
+                fn write_columns<D: typed_sql::dialect::Dialect>(sql: &mut typed_sql::Writer<D>) {
+                    #(#write_column_names)*
+                }
+            }
+
+            impl<A: typed_sql::table::Alias> typed_sql::table::Aliasable<A> for #ident {
+                type Fields = #fields_ident<typed_sql::table::Aliased<#ident, A>>;
+
+                type NullableFields = #nullable_fields_ident<typed_sql::table::Aliased<#ident, A>>;
             }
         };
 
@@ -67,7 +159,58 @@ pub fn table(input: TokenStream) -> TokenStream {
     }
 }
 
-#[proc_macro_derive(Join)]
+#[derive(Clone, Copy, PartialEq)]
+enum JoinKind {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Cross,
+}
+
+impl JoinKind {
+    fn from_field(field: &Field) -> syn::Result<Self> {
+        let attr = match field.attrs.iter().find(|attr| attr.path.is_ident("join")) {
+            Some(attr) => attr,
+            None => return Ok(JoinKind::Inner),
+        };
+
+        let kind: Ident = attr.parse_args()?;
+        match kind.to_string().as_str() {
+            "inner" => Ok(JoinKind::Inner),
+            "left" => Ok(JoinKind::Left),
+            "right" => Ok(JoinKind::Right),
+            "full" => Ok(JoinKind::Full),
+            "cross" => Ok(JoinKind::Cross),
+            _ => Err(syn::Error::new_spanned(
+                kind,
+                "expected one of `inner`, `left`, `right`, `full` or `cross`",
+            )),
+        }
+    }
+
+    fn marker(self) -> proc_macro2::TokenStream {
+        let ident = match self {
+            JoinKind::Inner => "Inner",
+            JoinKind::Left => "Left",
+            JoinKind::Right => "Right",
+            JoinKind::Full => "Full",
+            JoinKind::Cross => "Cross",
+        };
+        let ident = Ident::new(ident, Span::call_site());
+        quote! { typed_sql::query::select::join::#ident }
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    if let Type::Path(path) = ty {
+        matches!(path.path.segments.last(), Some(segment) if segment.ident == "Option")
+    } else {
+        false
+    }
+}
+
+#[proc_macro_derive(Join, attributes(join))]
 pub fn join(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -79,35 +222,83 @@ pub fn join(input: TokenStream) -> TokenStream {
         let ident = input.ident;
         let fields_ident = format_ident!("{}Fields", ident);
 
-        let struct_fields = fields.named.iter().map(|field| {
+        if fields.named.len() < 2 {
+            return TokenStream::from(
+                syn::Error::new_spanned(
+                    &fields,
+                    "a join needs at least two fields, the base table followed by the joined tables",
+                )
+                .to_compile_error(),
+            );
+        }
+
+        let kinds = match fields
+            .named
+            .iter()
+            .skip(1)
+            .map(JoinKind::from_field)
+            .collect::<syn::Result<Vec<_>>>()
+        {
+            Ok(kinds) => kinds,
+            Err(error) => return TokenStream::from(error.to_compile_error()),
+        };
+
+        // A table's columns are nullable if it's left or full joined,
+        // or if a later table is right or full joined.
+        let struct_fields = fields.named.iter().enumerate().map(|(i, field)| {
             let name = &field.ident;
             let ty = &field.ty;
-            quote! {
-                #name: <#ty as typed_sql::Table>::Fields
+            let is_outer = i > 0 && matches!(kinds[i - 1], JoinKind::Left | JoinKind::Full);
+            let is_nullable = is_outer
+                || kinds[i..]
+                    .iter()
+                    .any(|kind| matches!(kind, JoinKind::Right | JoinKind::Full));
+
+            if is_nullable {
+                quote! {
+                    #name: <#ty as typed_sql::Table>::NullableFields
+                }
+            } else {
+                quote! {
+                    #name: <#ty as typed_sql::Table>::Fields
+                }
             }
         });
 
         let mut fields = fields.named.iter();
         let table = &fields.next().unwrap().ty;
 
+        let joined = fields.zip(kinds.iter().copied());
+        let predicated = joined
+            .clone()
+            .enumerate()
+            .filter(|(_, (_, kind))| *kind != JoinKind::Cross)
+            .map(|(i, _)| i);
+
         let join_ident = format_ident!("{}Join", ident);
-        let join_fields = fields.clone().map(|field| {
+        let join_fields = joined.clone().enumerate().map(|(i, (field, kind))| {
             let name = &field.ident;
-            let g = field.ident.as_ref().unwrap().to_string().to_uppercase();
-            let g = format_ident!("{}", g);
             let ty = &field.ty;
-            quote! {
-                #name: typed_sql::query::select::join::Joined<#g, typed_sql::query::select::join::Inner, #ty>
+            let marker = kind.marker();
+            if kind == JoinKind::Cross {
+                quote! {
+                    #name: typed_sql::query::select::join::Joined<(), #marker, #ty>
+                }
+            } else {
+                let g = join_generic(i);
+                quote! {
+                    #name: typed_sql::query::select::join::Joined<#g, #marker, #ty>
+                }
             }
         });
 
-        let generics = fields.map(|field| {
-            Ident::new(
-                &field.ident.as_ref().unwrap().to_string().to_uppercase(),
-                Span::call_site(),
-            )
+        let write_joins = joined.clone().map(|(field, _)| {
+            let name = &field.ident;
+            quote! { self.#name.write_join(sql); }
         });
 
+        let generics = predicated.map(join_generic);
+
         let join_generics = generics.clone().map(|generic| {
             quote! {
                 #generic
@@ -146,8 +337,8 @@ pub fn join(input: TokenStream) -> TokenStream {
                 type Table = #table;
                 type Fields = #fields_ident;
 
-                fn write_join_select(&self, sql: &mut String) {
-                    self.post.write_join(sql);
+                fn write_join_select<D: typed_sql::dialect::Dialect>(&self, sql: &mut typed_sql::Writer<D>) {
+                    #(#write_joins)*
                 }
             }
         };
@@ -158,6 +349,12 @@ pub fn join(input: TokenStream) -> TokenStream {
     }
 }
 
+/// The generic predicate of the `i`th joined field, prefixed so it can't clash with
+/// the generics of the impls, such as `D` for the dialect.
+fn join_generic(i: usize) -> Ident {
+    format_ident!("__J{}", i)
+}
+
 #[proc_macro_derive(Insertable)]
 pub fn insertable(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -171,22 +368,30 @@ pub fn insertable(input: TokenStream) -> TokenStream {
 
         let write_columns = fields.named.iter().map(|field| {
             let name = &field.ident;
-            quote! { sql.push_str(stringify!(#name)); }
+            quote! { sql.write_ident(stringify!(#name)); }
         });
 
         let write_values = fields.named.iter().map(|field| {
             let name = &field.ident;
-            quote! { self.#name.write_primative(sql); }
+            quote! { sql.write_value(&self.#name); }
+        });
+
+        // The higher-ranked bounds are only checked where the impl is used,
+        // so structs with unchecked fields still compile.
+        let checked_bounds = fields.named.iter().map(|field| {
+            let ty = &field.ty;
+            quote! { for<'__checked> #ty: typed_sql::CheckedSql }
         });
 
         let expanded = quote! {
+            impl typed_sql::CheckedSql for #ident where #(#checked_bounds),* {}
+
             impl typed_sql::Insertable for #ident {
-                fn write_columns(sql: &mut String) {
+                fn write_columns<D: typed_sql::dialect::Dialect>(sql: &mut typed_sql::Writer<D>) {
                     #(#write_columns){sql.push(',');}*
                 }
 
-                fn write_values(&self, sql: &mut String) {
-                    use typed_sql::types::Primitive;
+                fn write_values<D: typed_sql::dialect::Dialect>(&self, sql: &mut typed_sql::Writer<D>) {
                     #(#write_values){sql.push(',');}*
                 }
             }
@@ -211,7 +416,8 @@ pub fn binding(input: TokenStream) -> TokenStream {
 
         let bind_fields = fields.named.iter().map(|field| {
             let name = &field.ident;
-            quote! { #name: typed_sql::types::Bind }
+            let ty = &field.ty;
+            quote! { #name: typed_sql::types::Bind<#ty> }
         });
 
         let binds = fields.named.iter().map(|field| {
@@ -219,9 +425,13 @@ pub fn binding(input: TokenStream) -> TokenStream {
             quote! { #name: binder.bind() }
         });
 
-        let values = fields.named.iter().map(|field| {
+        let values = fields.named.iter().enumerate().map(|(i, field)| {
             let name = &field.ident;
-            quote! { self.#name.write_primative(sql); }
+            let n = i + 1;
+            quote! {
+                f(sql, #n);
+                sql.write_value(&self.#name);
+            }
         });
 
         let expanded = quote! {
@@ -238,11 +448,15 @@ pub fn binding(input: TokenStream) -> TokenStream {
                     }
                 }
 
-                fn write_types(_sql: &mut String) {}
+                fn write_types<D: typed_sql::dialect::Dialect>(_sql: &mut typed_sql::Writer<D>) {}
 
-                fn write_values(&self, sql: &mut String) {
-                    use typed_sql::types::Primitive;
-                    #(#values){sql.push(',');}*;
+                #[allow(unused_mut, unused_variables)]
+                fn write_values_with<D, F>(&self, sql: &mut typed_sql::Writer<D>, mut f: F)
+                where
+                    D: typed_sql::dialect::Dialect,
+                    F: FnMut(&mut typed_sql::Writer<D>, usize),
+                {
+                    #(#values)*
                 }
             }
         };
@@ -252,27 +466,133 @@ pub fn binding(input: TokenStream) -> TokenStream {
     }
 }
 
-#[proc_macro_derive(Queryable)]
+fn queryable_table(input: &DeriveInput) -> syn::Result<Option<syn::Path>> {
+    let attr = match input
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("queryable"))
+    {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let key: Ident = input.parse()?;
+        if key != "table" {
+            return Err(syn::Error::new_spanned(key, "expected `table = ...`"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        input.parse().map(Some)
+    })
+}
+
+fn column_rename(field: &Field) -> syn::Result<Option<Ident>> {
+    let attr = match field.attrs.iter().find(|attr| attr.path.is_ident("column")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let key: Ident = input.parse()?;
+        if key != "rename" {
+            return Err(syn::Error::new_spanned(key, "expected `rename = \"...\"`"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let name: syn::LitStr = input.parse()?;
+        name.parse().map(Some)
+    })
+}
+
+#[proc_macro_derive(Queryable, attributes(queryable, column))]
 pub fn queryable(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    let table = match queryable_table(&input) {
+        Ok(table) => table,
+        Err(error) => return TokenStream::from(error.to_compile_error()),
+    };
+
     if let Data::Struct(DataStruct {
         fields: Fields::Named(fields),
         ..
     }) = input.data
     {
         let ident = &input.ident;
-        let columns = fields.named.iter().map(|field| {
-            let name = &field.ident;
-            quote! {
-                sql.push_str(stringify!(#name));
+
+        let renames = match fields
+            .named
+            .iter()
+            .map(column_rename)
+            .collect::<syn::Result<Vec<_>>>()
+        {
+            Ok(renames) => renames,
+            Err(error) => return TokenStream::from(error.to_compile_error()),
+        };
+
+        let columns = fields.named.iter().zip(&renames).map(|(field, rename)| {
+            let name = field.ident.as_ref().unwrap();
+            let column = rename.as_ref().unwrap_or(name);
+
+            let write_column = if table.is_some() {
+                quote! {
+                    typed_sql::query::select::WriteQueryable::write_query(&fields.#column, sql);
+                }
+            } else {
+                quote! {
+                    sql.write_ident(stringify!(#column));
+                }
+            };
+
+            if rename.is_some() {
+                quote! {
+                    #write_column
+                    sql.push_str(" AS ");
+                    sql.write_ident(stringify!(#name));
+                }
+            } else {
+                write_column
             }
         });
 
-        let expanded = quote! {
-            impl typed_sql::Queryable for #ident {
-                fn write_queryable(sql: &mut String) {
-                    #(#columns)(sql.push(','))*
+        let expanded = if let Some(table) = &table {
+            // Each field is read from the column of the same name in the table,
+            // which must exist and have a type the field can hold.
+            let checks = fields.named.iter().zip(&renames).map(|(field, rename)| {
+                let name = field.ident.as_ref().unwrap();
+                let column = rename.as_ref().unwrap_or(name);
+                let ty = &field.ty;
+                quote_spanned! {field.ty.span()=>
+                    read_as::<_, _, #ty>(fields.#column);
+                }
+            });
+
+            quote! {
+                const _: () = {
+                    fn read_as<T, A, B>(_: typed_sql::types::Field<T, A>)
+                    where
+                        A: typed_sql::query::select::queryable::ReadAs<B>,
+                    {
+                    }
+
+                    #[allow(dead_code)]
+                    fn check(fields: <#table as typed_sql::Table>::Fields) {
+                        #(#checks)*
+                    }
+                };
+
+                impl typed_sql::Queryable for #ident {
+                    fn write_queryable<D: typed_sql::dialect::Dialect>(sql: &mut typed_sql::Writer<D>) {
+                        let fields = <<#table as typed_sql::Table>::Fields as Default>::default();
+                        #(#columns){sql.push(',');}*
+                    }
+                }
+            }
+        } else {
+            quote! {
+                impl typed_sql::Queryable for #ident {
+                    fn write_queryable<D: typed_sql::dialect::Dialect>(sql: &mut typed_sql::Writer<D>) {
+                        #(#columns){sql.push(',');}*
+                    }
                 }
             }
         };